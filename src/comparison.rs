@@ -0,0 +1,344 @@
+//! Analytic comparisons between a control and an experimental arm.
+
+use alloc::vec::Vec;
+
+use crate::{PiecewiseExponential, PiecewiseExponentialError, net_event_probability};
+
+/// Compute the number needed to treat at horizon `tau`.
+///
+/// The value is `1 / (S_exp(tau) - S_ctrl(tau))`. A positive result is the number of patients
+/// that must receive the experimental treatment to avoid one additional event by `tau`. A
+/// negative result means the experimental arm is worse, and its magnitude is the number needed
+/// to harm. Equal survival at `tau` returns `f64::INFINITY`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, number_needed_to_treat};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let nnt = number_needed_to_treat(&control, &experimental, 12.0);
+/// assert!(nnt > 0.0);
+/// ```
+pub fn number_needed_to_treat(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    tau: f64,
+) -> f64 {
+    let difference = experimental.survival(tau) - control.survival(tau);
+    if difference == 0.0 {
+        return f64::INFINITY;
+    }
+    1.0 / difference
+}
+
+/// Compute the restricted mean survival time difference `∫_0^tau (S_exp(t) - S_ctrl(t)) dt`.
+///
+/// This is the analytic target of an RMST test; a positive value favors the experimental arm.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, survival_difference_integral};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let difference = survival_difference_integral(&control, &experimental, 12.0);
+/// assert!(difference > 0.0);
+/// ```
+pub fn survival_difference_integral(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    tau: f64,
+) -> f64 {
+    experimental.rmst(tau) - control.rmst(tau)
+}
+
+/// Compute the restricted mean survival time difference `RMST_exp(tau) - RMST_ctrl(tau)` at each
+/// of `taus`.
+///
+/// The survival difference is integrated once across the union breakpoint grid, with each
+/// horizon reading off the running total, so the cost is linear in the number of breakpoints and
+/// horizons rather than their product. A horizon of `f64::INFINITY` gives the difference in
+/// means.
+///
+/// # Errors
+///
+/// Returns [`PiecewiseExponentialError::InvalidHorizon`] when a horizon is `NaN`, negative, or
+/// smaller than the one before it.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, rmst_difference_curve};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let curve = rmst_difference_curve(&control, &experimental, &[6.0, 12.0, 24.0]).unwrap();
+/// assert!(curve.windows(2).all(|pair| pair[0] < pair[1]));
+/// ```
+pub fn rmst_difference_curve(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    taus: &[f64],
+) -> Result<Vec<f64>, PiecewiseExponentialError> {
+    let mut previous = 0.0;
+    for (index, &tau) in taus.iter().enumerate() {
+        if tau.is_nan() || tau < previous {
+            return Err(PiecewiseExponentialError::InvalidHorizon { index });
+        }
+        previous = tau;
+    }
+
+    let breakpoints = union_breakpoints(control, experimental, f64::INFINITY);
+    let mut next = 1;
+    let mut position = 0.0;
+    let mut difference = 0.0;
+    Ok(taus
+        .iter()
+        .map(|&tau| {
+            while position < tau {
+                let end = breakpoints
+                    .get(next)
+                    .copied()
+                    .filter(|&breakpoint| breakpoint < tau)
+                    .unwrap_or(tau);
+                difference += experimental.rmst_over_segment(position, end)
+                    - control.rmst_over_segment(position, end);
+                if breakpoints.get(next) == Some(&end) {
+                    next += 1;
+                }
+                position = end;
+            }
+            difference
+        })
+        .collect())
+}
+
+/// Evaluate the hazard ratio `h_exp(t) / h_ctrl(t)` at each of `times`.
+///
+/// Both hazards are right-continuous, so a time on a breakpoint uses the rates of the interval it
+/// opens. A zero control hazard, which only occurs before time zero, yields `f64::INFINITY`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, hazard_ratio_grid};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.06]).unwrap();
+/// let ratios = hazard_ratio_grid(&control, &experimental, &[1.0, 6.0]);
+/// assert!((ratios[0] - 1.0).abs() < 1e-12);
+/// assert!((ratios[1] - 0.6).abs() < 1e-12);
+/// ```
+pub fn hazard_ratio_grid(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    times: &[f64],
+) -> Vec<f64> {
+    times
+        .iter()
+        .map(|&t| {
+            let reference = control.hazard_at(t);
+            if reference == 0.0 {
+                f64::INFINITY
+            } else {
+                experimental.hazard_at(t) / reference
+            }
+        })
+        .collect()
+}
+
+/// Compute the win probability `P(T_exp > T_ctrl)` for independent arms.
+///
+/// The value is `∫_0^∞ S_exp(t) f_ctrl(t) dt`, the probability that a control failure occurs
+/// first when the experimental arm competes as dropout, evaluated in closed form on the union of
+/// both breakpoint grids. Identical distributions return `0.5`. Truncated arms are integrated as
+/// described for [`net_event_probability`].
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, win_probability};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let probability = win_probability(&control, &experimental);
+/// assert!((probability - 2.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn win_probability(control: &PiecewiseExponential, experimental: &PiecewiseExponential) -> f64 {
+    net_event_probability(control, experimental, f64::INFINITY)
+}
+
+/// Build the pooled distribution whose hazard is the allocation-weighted average of two arms.
+///
+/// On the union of both breakpoint grids the pooled rate is
+/// `(n_ctrl * h_ctrl + n_exp * h_exp) / (n_ctrl + n_exp)`. Unlike a survival-weighted pooling,
+/// the weights stay fixed at the allocation fractions rather than following who remains at risk.
+/// The pooled distribution ends where the later of the two final intervals ends, so a finite
+/// boundary shared by both arms is kept.
+///
+/// # Errors
+///
+/// Returns [`PiecewiseExponentialError::InvalidArmCounts`] unless both counts are non-negative
+/// and finite with a positive total, and [`PiecewiseExponentialError::TruncatedHazard`] when
+/// either arm is [`truncate`](PiecewiseExponential::truncate)d, since its hazard is not constant
+/// on the grid.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, pooled_by_counts};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let pooled = pooled_by_counts(&control, &experimental, 100.0, 200.0).unwrap();
+/// assert!((pooled.hazard_at(0.0) - 0.4 / 3.0).abs() < 1e-15);
+/// ```
+pub fn pooled_by_counts(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    n_ctrl: f64,
+    n_exp: f64,
+) -> Result<PiecewiseExponential, PiecewiseExponentialError> {
+    let total = n_ctrl + n_exp;
+    if !(n_ctrl >= 0.0 && n_exp >= 0.0 && total > 0.0 && total.is_finite()) {
+        return Err(PiecewiseExponentialError::InvalidArmCounts);
+    }
+    if control.is_truncated() || experimental.is_truncated() {
+        return Err(PiecewiseExponentialError::TruncatedHazard);
+    }
+
+    let starts = union_breakpoints(control, experimental, f64::INFINITY);
+    let final_end = final_boundary(control).max(final_boundary(experimental));
+    let durations: Vec<f64> = starts
+        .iter()
+        .enumerate()
+        .map(|(idx, &start)| starts.get(idx + 1).copied().unwrap_or(final_end) - start)
+        .collect();
+    let rates: Vec<f64> = starts
+        .iter()
+        .map(|&start| {
+            (n_ctrl * control.hazard_at(start) + n_exp * experimental.hazard_at(start)) / total
+        })
+        .collect();
+    PiecewiseExponential::new(&durations, &rates)
+}
+
+/// Find every time in `(0, tau]` at which the two survival curves cross.
+///
+/// On each segment of the union breakpoint grid both log-survival curves are linear, so the
+/// cumulative hazard difference `H_exp(t) - H_ctrl(t)` is checked for a sign change at the
+/// segment ends and the crossing is solved exactly. Only a strict sign change counts: curves that
+/// touch without changing order are not crossings, and curves that coincide over a stretch cross
+/// at its start only when their order differs on either side. A difference within rounding of
+/// zero at a breakpoint is treated as zero.
+///
+/// # Errors
+///
+/// Returns [`PiecewiseExponentialError::TruncatedHazard`] when either arm is
+/// [`truncate`](PiecewiseExponential::truncate)d, since its log-survival is not linear between
+/// breakpoints.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, survival_crossing_times};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.2, 0.02]).unwrap();
+/// let crossings = survival_crossing_times(&control, &experimental, 24.0).unwrap();
+/// assert_eq!(crossings.len(), 1);
+/// assert!((crossings[0] - 6.75).abs() < 1e-12);
+/// ```
+pub fn survival_crossing_times(
+    control: &PiecewiseExponential,
+    experimental: &PiecewiseExponential,
+    tau: f64,
+) -> Result<Vec<f64>, PiecewiseExponentialError> {
+    if control.is_truncated() || experimental.is_truncated() {
+        return Err(PiecewiseExponentialError::TruncatedHazard);
+    }
+    let difference = |t: f64| {
+        let (exp_hazard, ctrl_hazard) = (
+            experimental.cumulative_hazard_at(t),
+            control.cumulative_hazard_at(t),
+        );
+        let value = exp_hazard - ctrl_hazard;
+        if value.abs() <= 4.0 * f64::EPSILON * (exp_hazard + ctrl_hazard) {
+            0.0
+        } else {
+            value
+        }
+    };
+    let slope_at = |t: f64| experimental.hazard_at(t) - control.hazard_at(t);
+
+    let grid = union_breakpoints(control, experimental, tau);
+    let Some(&last) = grid.last() else {
+        return Ok(Vec::new());
+    };
+    // The difference at each breakpoint and at `tau`, or its limit when `tau` is infinite.
+    let mut points: Vec<(f64, f64)> = grid.iter().map(|&t| (t, difference(t))).collect();
+    points.push(if tau.is_finite() {
+        (tau, difference(tau))
+    } else if slope_at(last) == 0.0 {
+        (tau, difference(last))
+    } else {
+        (tau, slope_at(last) * f64::INFINITY)
+    });
+
+    let mut crossings = Vec::new();
+    // Sign of the difference before the current stretch of zeros, and where that stretch began.
+    let mut sign = 0.0;
+    let mut touch = None;
+    for (idx, &(at, value)) in points.iter().enumerate() {
+        if let Some(&(start, previous)) = idx.checked_sub(1).map(|prev| &points[prev]) {
+            if previous != 0.0 && value != 0.0 && previous.signum() != value.signum() {
+                crossings.push(start - previous / slope_at(start));
+            }
+        }
+        if value == 0.0 {
+            touch.get_or_insert(at);
+        } else {
+            if let Some(zero) = touch.take() {
+                if sign != 0.0 && value.signum() != sign {
+                    crossings.push(zero);
+                }
+            }
+            sign = value.signum();
+        }
+    }
+    // A zero reached exactly at `tau` is a crossing when the order flips right after it.
+    if touch == Some(tau) && sign != 0.0 {
+        let after = slope_at(tau);
+        if after != 0.0 && after.signum() != sign {
+            crossings.push(tau);
+        }
+    }
+    Ok(crossings)
+}
+
+/// Return the end of the final interval, `f64::INFINITY` for an open tail.
+fn final_boundary(dist: &PiecewiseExponential) -> f64 {
+    dist.intervals()
+        .last()
+        .map_or(f64::INFINITY, |interval| interval.end)
+}
+
+/// Collect the sorted, distinct interval starts of two distributions that fall below `tau`.
+pub(crate) fn union_breakpoints(
+    first: &PiecewiseExponential,
+    second: &PiecewiseExponential,
+    tau: f64,
+) -> Vec<f64> {
+    let mut grid: Vec<f64> = first
+        .intervals()
+        .chain(second.intervals())
+        .map(|interval| interval.start)
+        .filter(|&start| start < tau)
+        .collect();
+    grid.sort_by(f64::total_cmp);
+    grid.dedup();
+    grid
+}
@@ -0,0 +1,65 @@
+//! Analytic event probabilities when failure competes with dropout.
+
+use crate::PiecewiseExponential;
+use crate::comparison::union_breakpoints;
+use crate::math;
+
+/// Compute the probability of observing a failure before dropout and before `tau`.
+///
+/// The value is `∫_0^tau h_f(t) S_f(t) S_d(t) dt`, evaluated in closed form on the union of the
+/// breakpoints of `failure` and `dropout`, where both hazards are constant. Non-positive `tau`
+/// returns `0`, and `tau = f64::INFINITY` yields the overall probability that failure precedes
+/// dropout.
+///
+/// A [`truncate`](PiecewiseExponential::truncate)d arm is handled through its untruncated law:
+/// with truncation masses `m_f` and `m_d` and `S_d(end_d) = 1 - m_d`, the value is
+/// `(∫_0^u f_f(t) S_d(t) dt - (1 - m_d) F_f(u)) / (m_f m_d)` for the untruncated functions and
+/// `u` the earliest of `tau` and both truncation points.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, net_event_probability};
+///
+/// let failure = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let dropout = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let probability = net_event_probability(&failure, &dropout, f64::INFINITY);
+/// assert!((probability - 0.5).abs() < 1e-12);
+/// ```
+pub fn net_event_probability(
+    failure: &PiecewiseExponential,
+    dropout: &PiecewiseExponential,
+    tau: f64,
+) -> f64 {
+    let upper = tau.min(failure.support_end()).min(dropout.support_end());
+    let grid = union_breakpoints(failure, dropout, upper);
+
+    let mut total = 0.0;
+    for (idx, &start) in grid.iter().enumerate() {
+        let end = grid.get(idx + 1).copied().unwrap_or(upper);
+        let failure_rate = failure.base_hazard_at(start);
+        let combined_rate = failure_rate + dropout.base_hazard_at(start);
+        let at_risk = math::exp(
+            -(failure.base_cumulative_hazard_at(start) + dropout.base_cumulative_hazard_at(start)),
+        );
+        total +=
+            at_risk * failure_rate / combined_rate * -math::exp_m1(-combined_rate * (end - start));
+    }
+    if !(failure.is_truncated() || dropout.is_truncated()) {
+        return total;
+    }
+
+    // Dropout survival beyond its truncation point is removed, and the rest is renormalized.
+    let dropout_floor = if dropout.is_truncated() {
+        math::exp(-dropout.base_cumulative_hazard_at(dropout.support_end()))
+    } else {
+        0.0
+    };
+    let failure_by_upper = if upper > 0.0 {
+        -math::exp_m1(-failure.base_cumulative_hazard_at(upper))
+    } else {
+        0.0
+    };
+    (total - dropout_floor * failure_by_upper)
+        / (failure.truncation_mass() * dropout.truncation_mass())
+}
@@ -0,0 +1,179 @@
+//! Efficacy boundaries for group sequential designs.
+
+use alloc::vec::Vec;
+
+use crate::math;
+use crate::normal;
+
+/// Lower end of the integration range for the continuation region.
+const LOWER_LIMIT: f64 = -12.0;
+/// Largest boundary considered; the null crossing probability beyond it underflows.
+const UPPER_LIMIT: f64 = 40.0;
+/// Number of Simpson panels used to integrate over the continuation region at each look.
+const PANELS: usize = 600;
+
+/// Compute one-sided efficacy z-boundaries from the Lan-DeMets O'Brien-Fleming spending function.
+///
+/// The cumulative type I error spent by information fraction `t` is
+/// `2 - 2 * Phi(Phi^-1(1 - alpha / 2) / sqrt(t))`. Each boundary is chosen so that the
+/// probability under the null of first crossing at that look equals the newly spent error.
+///
+/// # Panics
+///
+/// Panics unless `0 < alpha < 1` and the information fractions are strictly increasing within
+/// `(0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::obrien_fleming_boundaries;
+///
+/// let boundaries = obrien_fleming_boundaries(&[1.0 / 3.0, 2.0 / 3.0, 1.0], 0.025);
+/// assert!((boundaries[0] - 3.7103).abs() < 1e-3);
+/// assert!((boundaries[2] - 1.9930).abs() < 1e-3);
+/// ```
+pub fn obrien_fleming_boundaries(info_fractions: &[f64], alpha: f64) -> Vec<f64> {
+    let critical = normal::quantile(1.0 - 0.5 * alpha);
+    spending_boundaries(info_fractions, alpha, |t| {
+        2.0 * normal::upper_tail(critical / math::sqrt(t))
+    })
+}
+
+/// Compute one-sided efficacy z-boundaries from the Lan-DeMets Pocock spending function.
+///
+/// The cumulative type I error spent by information fraction `t` is
+/// `alpha * ln(1 + (e - 1) * t)`, which yields boundaries that are nearly constant on the
+/// z-scale for equally spaced looks.
+///
+/// # Panics
+///
+/// Panics unless `0 < alpha < 1` and the information fractions are strictly increasing within
+/// `(0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::pocock_boundaries;
+///
+/// let boundaries = pocock_boundaries(&[1.0 / 3.0, 2.0 / 3.0, 1.0], 0.025);
+/// assert!((boundaries[0] - boundaries[2]).abs() < 0.05);
+/// ```
+pub fn pocock_boundaries(info_fractions: &[f64], alpha: f64) -> Vec<f64> {
+    spending_boundaries(info_fractions, alpha, |t| {
+        alpha * math::ln_1p((core::f64::consts::E - 1.0) * t)
+    })
+}
+
+/// Solve for the boundaries that spend `spending(t)` cumulative type I error by fraction `t`.
+///
+/// The null density of the z-statistic on the continuation region is carried from look to look
+/// with the recursive numerical integration of Armitage, McPherson, and Rowe, using Simpson's
+/// rule on a fixed grid.
+fn spending_boundaries<F>(info_fractions: &[f64], alpha: f64, spending: F) -> Vec<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    assert!(
+        alpha > 0.0 && alpha < 1.0,
+        "alpha must lie strictly between 0 and 1"
+    );
+    assert!(
+        info_fractions
+            .iter()
+            .zip(core::iter::once(&0.0).chain(info_fractions))
+            .all(|(&t, &previous)| t > previous && t <= 1.0),
+        "information fractions must be strictly increasing within (0, 1]"
+    );
+
+    let mut boundaries = Vec::with_capacity(info_fractions.len());
+    // Grid points, Simpson-weighted densities, and the information fraction of the last look.
+    let mut grid: Vec<f64> = Vec::new();
+    let mut weighted_density: Vec<f64> = Vec::new();
+    let mut previous_fraction = 0.0;
+    let mut spent = 0.0;
+
+    for &fraction in info_fractions {
+        let target = spending(fraction) - spent;
+        spent += target;
+
+        let crossing = |boundary: f64| -> f64 {
+            if boundaries.is_empty() {
+                return normal::upper_tail(boundary);
+            }
+            let scale = math::sqrt(fraction);
+            let spread = math::sqrt(fraction - previous_fraction);
+            let drift = math::sqrt(previous_fraction);
+            grid.iter()
+                .zip(&weighted_density)
+                .map(|(&u, &weight)| {
+                    weight * normal::upper_tail((boundary * scale - u * drift) / spread)
+                })
+                .sum()
+        };
+
+        let boundary = if target <= 0.0 {
+            f64::INFINITY
+        } else {
+            // The crossing probability falls as the boundary rises, so bisect on it.
+            let (mut lower, mut upper) = (LOWER_LIMIT, UPPER_LIMIT);
+            for _ in 0..200 {
+                let mid = 0.5 * (lower + upper);
+                if mid <= lower || mid >= upper {
+                    break;
+                }
+                if crossing(mid) > target {
+                    lower = mid;
+                } else {
+                    upper = mid;
+                }
+            }
+            0.5 * (lower + upper)
+        };
+
+        boundaries.push(boundary);
+        if boundaries.len() == info_fractions.len() {
+            break;
+        }
+
+        // Carry the density of the continuation region forward to this look.
+        let upper = boundary.min(-LOWER_LIMIT);
+        let step = (upper - LOWER_LIMIT) / (2 * PANELS) as f64;
+        let next_grid: Vec<f64> = (0..=2 * PANELS)
+            .map(|idx| LOWER_LIMIT + idx as f64 * step)
+            .collect();
+        let next_density: Vec<f64> = next_grid
+            .iter()
+            .map(|&z| {
+                if grid.is_empty() {
+                    return normal::pdf(z);
+                }
+                let scale = math::sqrt(fraction);
+                let spread = math::sqrt(fraction - previous_fraction);
+                let drift = math::sqrt(previous_fraction);
+                grid.iter()
+                    .zip(&weighted_density)
+                    .map(|(&u, &weight)| {
+                        weight * scale / spread * normal::pdf((z * scale - u * drift) / spread)
+                    })
+                    .sum()
+            })
+            .collect();
+        weighted_density = next_density
+            .iter()
+            .enumerate()
+            .map(|(idx, &density)| {
+                let simpson = if idx == 0 || idx == 2 * PANELS {
+                    1.0
+                } else if idx % 2 == 1 {
+                    4.0
+                } else {
+                    2.0
+                };
+                density * simpson * step / 3.0
+            })
+            .collect();
+        grid = next_grid;
+        previous_fraction = fraction;
+    }
+    boundaries
+}
@@ -1,12 +1,45 @@
+#![no_std]
 #![warn(missing_docs)]
 
 //! Clinical trial simulation
 //!
 //! This crate ports core survival distribution sampling routines from the R
 //! simtrial package to Rust for fast simulation workflows.
+//!
+//! The sampling core only needs `alloc`. Disable the default `std` feature and
+//! enable `libm` to use the crate in `no_std` environments.
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled");
+
+mod comparison;
+mod competing_risks;
+mod group_sequential;
+mod math;
+mod multiplicity;
+mod normal;
 mod piecewise_exponential;
+mod power;
+mod seeding;
+mod stratification;
+mod weights;
 
+pub use comparison::{
+    hazard_ratio_grid, number_needed_to_treat, pooled_by_counts, rmst_difference_curve,
+    survival_crossing_times, survival_difference_integral, win_probability,
+};
+pub use competing_risks::net_event_probability;
+pub use group_sequential::{obrien_fleming_boundaries, pocock_boundaries};
+pub use multiplicity::{Adjustment, PValueError, adjust_pvalues};
 pub use piecewise_exponential::{
-    PiecewiseExponential, PiecewiseExponentialError, PiecewiseExponentialSampleError,
+    HazardShape, Interval, PiecewiseExponential, PiecewiseExponentialError,
+    PiecewiseExponentialSampleError, QuantileMode,
 };
+pub use power::{detectable_hr, events_for_power, power_confidence_interval};
+pub use seeding::seed_for_replicate;
+pub use stratification::stratification_design_effect;
+pub use weights::fh_weights;
@@ -0,0 +1,71 @@
+//! Floating-point functions backed by `std` when available and `libm` otherwise.
+
+#[cfg(feature = "std")]
+pub(crate) fn ln(x: f64) -> f64 {
+    x.ln()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn ln(x: f64) -> f64 {
+    libm::log(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn exp(x: f64) -> f64 {
+    libm::exp(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn ln_1p(x: f64) -> f64 {
+    x.ln_1p()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn ln_1p(x: f64) -> f64 {
+    libm::log1p(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn exp_m1(x: f64) -> f64 {
+    x.exp_m1()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn exp_m1(x: f64) -> f64 {
+    libm::expm1(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    x.powf(y)
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn powf(x: f64, y: f64) -> f64 {
+    libm::pow(x, y)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn round(x: f64) -> f64 {
+    x.round()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn round(x: f64) -> f64 {
+    libm::round(x)
+}
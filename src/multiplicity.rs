@@ -0,0 +1,87 @@
+//! Multiplicity adjustments for p-values across endpoints.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Procedure used by [`adjust_pvalues`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    /// Multiply each p-value by the number of hypotheses.
+    Bonferroni,
+    /// Holm's step-down procedure, uniformly more powerful than Bonferroni.
+    Holm,
+}
+
+/// Adjust p-values for multiple testing, returning them in input order.
+///
+/// Adjusted values are capped at `1`. For [`Adjustment::Holm`], the `i`-th smallest p-value is
+/// multiplied by `m - i + 1` and the running maximum is taken so the adjusted values keep the
+/// order of the raw ones.
+///
+/// # Errors
+///
+/// Returns [`PValueError::OutOfRange`] when any p-value lies outside `[0, 1]` or is `NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{Adjustment, adjust_pvalues};
+///
+/// let pvalues = [0.01, 0.04, 0.03];
+/// let bonferroni = adjust_pvalues(&pvalues, Adjustment::Bonferroni).unwrap();
+/// assert_eq!(bonferroni, [0.03, 0.12, 0.09]);
+/// assert_eq!(adjust_pvalues(&pvalues, Adjustment::Holm).unwrap(), [0.03, 0.06, 0.06]);
+/// ```
+pub fn adjust_pvalues(pvalues: &[f64], method: Adjustment) -> Result<Vec<f64>, PValueError> {
+    if let Some((index, &value)) = pvalues
+        .iter()
+        .enumerate()
+        .find(|(_, p)| !(0.0..=1.0).contains(*p))
+    {
+        return Err(PValueError::OutOfRange { index, value });
+    }
+    let m = pvalues.len() as f64;
+    Ok(match method {
+        Adjustment::Bonferroni => pvalues.iter().map(|&p| (p * m).min(1.0)).collect(),
+        Adjustment::Holm => {
+            let mut order: Vec<usize> = (0..pvalues.len()).collect();
+            order.sort_by(|&a, &b| pvalues[a].total_cmp(&pvalues[b]));
+
+            let mut adjusted = alloc::vec![0.0; pvalues.len()];
+            let mut running_max = 0.0_f64;
+            for (rank, &idx) in order.iter().enumerate() {
+                let scaled = (pvalues[idx] * (m - rank as f64)).min(1.0);
+                running_max = running_max.max(scaled);
+                adjusted[idx] = running_max;
+            }
+            adjusted
+        }
+    })
+}
+
+/// Errors emitted by [`adjust_pvalues`] for invalid p-values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PValueError {
+    /// A p-value lies outside `[0, 1]` or is `NaN`.
+    OutOfRange {
+        /// Index of the offending p-value.
+        index: usize,
+        /// The offending p-value.
+        value: f64,
+    },
+}
+
+impl fmt::Display for PValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PValueError::OutOfRange { index, value } => write!(
+                f,
+                "p-value {} at index {} must lie within [0, 1]",
+                value, index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PValueError {}
@@ -0,0 +1,142 @@
+//! Standard normal distribution functions.
+
+use crate::math;
+
+const FRAC_1_SQRT_2PI: f64 = 0.398_942_280_401_432_7;
+
+/// Density of the standard normal distribution.
+pub(crate) fn pdf(z: f64) -> f64 {
+    FRAC_1_SQRT_2PI * math::exp(-0.5 * z * z)
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+pub(crate) fn cdf(z: f64) -> f64 {
+    0.5 * erfc(-z * core::f64::consts::FRAC_1_SQRT_2)
+}
+
+/// Upper tail probability `1 - cdf(z)`, accurate far into the tail.
+pub(crate) fn upper_tail(z: f64) -> f64 {
+    0.5 * erfc(z * core::f64::consts::FRAC_1_SQRT_2)
+}
+
+/// Quantile function of the standard normal distribution.
+///
+/// Acklam's rational approximation is refined with one Halley step, which brings the result to
+/// near machine precision.
+pub(crate) fn quantile(p: f64) -> f64 {
+    if p.is_nan() || !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let mut z = if p < P_LOW {
+        tail(math::sqrt(-2.0 * math::ln(p)))
+    } else if p > 1.0 - P_LOW {
+        -tail(math::sqrt(-2.0 * math::ln_1p(-p)))
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+
+    // Work on the smaller tail so the residual keeps its relative precision.
+    let error = if p < 0.5 {
+        cdf(z) - p
+    } else {
+        (1.0 - p) - upper_tail(z)
+    };
+    let step = error / pdf(z);
+    z -= step / (1.0 + 0.5 * z * step);
+    z
+}
+
+/// Complementary error function.
+///
+/// Small arguments use the everywhere-positive series
+/// `erf(x) = 2 / sqrt(pi) * exp(-x^2) * sum(2^n x^(2n + 1) / (2n + 1)!!)`, and large arguments
+/// use the Laplace continued fraction evaluated with the modified Lentz algorithm.
+fn erfc(x: f64) -> f64 {
+    const FRAC_2_SQRT_PI: f64 = core::f64::consts::FRAC_2_SQRT_PI;
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    if x < 3.0 {
+        let x2 = x * x;
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > sum * f64::EPSILON {
+            n += 1.0;
+            term *= 2.0 * x2 / (2.0 * n + 1.0);
+            sum += term;
+        }
+        return 1.0 - FRAC_2_SQRT_PI * math::exp(-x2) * sum;
+    }
+    if x > 27.0 {
+        return 0.0;
+    }
+
+    // erfc(x) = exp(-x^2) / sqrt(pi) / (x + (1/2) / (x + 1 / (x + (3/2) / (x + 2 / (x + ...))))).
+    const TINY: f64 = 1e-300;
+    let mut f = x;
+    let mut c = x;
+    let mut d = 0.0;
+    for k in 1..200 {
+        let a = 0.5 * k as f64;
+        d = x + a * d;
+        d = if d == 0.0 { TINY } else { d };
+        c = x + a / c;
+        c = if c == 0.0 { TINY } else { c };
+        d = 1.0 / d;
+        let delta = c * d;
+        f *= delta;
+        if (delta - 1.0).abs() < f64::EPSILON {
+            break;
+        }
+    }
+    0.5 * FRAC_2_SQRT_PI * math::exp(-x * x) / f
+}
@@ -1,6 +1,12 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 use rand::Rng;
-use rand::distr::Open01;
-use std::fmt;
+use rand::distr::{Distribution, Open01};
+use rand_distr::{Beta, Gamma};
+
+use crate::comparison::union_breakpoints;
+use crate::math;
 
 /// Piecewise exponential distribution sampled via the inverse cumulative distribution.
 ///
@@ -24,6 +30,8 @@ pub struct PiecewiseExponential {
     rates: Vec<f64>,
     cumulative_time: Vec<f64>,
     cumulative_hazard: Vec<f64>,
+    final_end: f64,
+    support_end: f64,
 }
 
 impl PiecewiseExponential {
@@ -123,9 +131,420 @@ impl PiecewiseExponential {
             rates: rates.to_vec(),
             cumulative_time,
             cumulative_hazard,
+            final_end: time_acc + durations[last_index],
+            support_end: f64::INFINITY,
+        })
+    }
+
+    /// Restrict the distribution to `[0, t_max]` and renormalize its mass.
+    ///
+    /// The returned distribution has survival `(S(t) - S(t_max)) / (1 - S(t_max))`, so every draw
+    /// falls in `(0, t_max]`. Rates of intervals starting before `t_max` are preserved and the
+    /// remaining intervals, including any open tail, are dropped. Truncating an already truncated
+    /// distribution keeps the earlier of the two boundaries.
+    ///
+    /// The truncated hazard `h(t) S(t) / (S(t) - S(t_max))` rises within every interval and is no
+    /// longer piecewise constant. [`intervals`](Self::intervals) still reports the rates before
+    /// truncation, and operations that need a piecewise constant hazard reject truncated inputs;
+    /// see [`is_truncated`](Self::is_truncated).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::InvalidTruncation`] unless `t_max` is positive and
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let truncated = dist.truncate(1.5).unwrap();
+    /// assert_eq!(truncated.survival(0.0), 1.0);
+    /// assert_eq!(truncated.survival(1.5), 0.0);
+    /// assert_eq!(truncated.inverse_cdf(f64::MIN_POSITIVE).unwrap(), 1.5);
+    /// ```
+    pub fn truncate(&self, t_max: f64) -> Result<Self, PiecewiseExponentialError> {
+        if !(t_max.is_finite() && t_max > 0.0) {
+            return Err(PiecewiseExponentialError::InvalidTruncation);
+        }
+        let support_end = t_max.min(self.support_end);
+        let kept = self
+            .cumulative_time
+            .partition_point(|&start| start < support_end);
+        Ok(Self {
+            rates: self.rates[..kept].to_vec(),
+            cumulative_time: self.cumulative_time[..kept].to_vec(),
+            cumulative_hazard: self.cumulative_hazard[..kept].to_vec(),
+            final_end: support_end,
+            support_end,
+        })
+    }
+
+    /// Rescale the time axis by `factor`, yielding the distribution of `factor * T`.
+    ///
+    /// Durations, including any truncation point, are multiplied by `factor` and rates are
+    /// divided by it, so converting a distribution in years to months uses `factor = 12`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::InvalidScaleFactor`] unless `factor` is positive and
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let years = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.6, 0.3]).unwrap();
+    /// let months = years.rescale_time(12.0).unwrap();
+    /// assert!((months.survival(12.0) - years.survival(1.0)).abs() < 1e-15);
+    /// ```
+    pub fn rescale_time(&self, factor: f64) -> Result<Self, PiecewiseExponentialError> {
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(PiecewiseExponentialError::InvalidScaleFactor);
+        }
+        Ok(Self {
+            rates: self.rates.iter().map(|&rate| rate / factor).collect(),
+            cumulative_time: self
+                .cumulative_time
+                .iter()
+                .map(|&time| time * factor)
+                .collect(),
+            cumulative_hazard: self.cumulative_hazard.clone(),
+            final_end: self.final_end * factor,
+            support_end: self.support_end * factor,
         })
     }
 
+    /// Build a single exponential distribution whose restricted mean survival time over
+    /// `[0, tau]` equals `target_rmst`.
+    ///
+    /// The rate is found by bisection on `(1 - exp(-rate * tau)) / rate = target_rmst`, which is
+    /// monotone decreasing in the rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::InvalidRmstTarget`] unless `tau` is finite and
+    /// `0 < target_rmst < tau`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::exponential_for_rmst(8.0, 12.0).unwrap();
+    /// assert!((dist.rmst(12.0) - 8.0).abs() < 1e-9);
+    /// ```
+    pub fn exponential_for_rmst(
+        target_rmst: f64,
+        tau: f64,
+    ) -> Result<Self, PiecewiseExponentialError> {
+        if !(tau.is_finite() && target_rmst > 0.0 && target_rmst < tau) {
+            return Err(PiecewiseExponentialError::InvalidRmstTarget);
+        }
+
+        // Solve on the dimensionless scale x = rate * tau, where the RMST fraction
+        // (1 - exp(-x)) / x falls from 1 towards 0 and is bounded above by 1 / x.
+        let fraction = target_rmst / tau;
+        let rmst_fraction = |x: f64| -math::exp_m1(-x) / x;
+        let mut lower = 0.0_f64;
+        let mut upper = 1.0 / fraction;
+        for _ in 0..200 {
+            let mid = 0.5 * (lower + upper);
+            if mid <= lower || mid >= upper {
+                break;
+            }
+            if rmst_fraction(mid) > fraction {
+                lower = mid;
+            } else {
+                upper = mid;
+            }
+        }
+
+        Self::new(&[f64::INFINITY], &[0.5 * (lower + upper) / tau])
+    }
+
+    /// Build a distribution that passes through the supplied `(time, survival)` milestones.
+    ///
+    /// Breakpoints are placed at the milestone times and each interval takes the constant hazard
+    /// `ln(S_prev / S) / (t - t_prev)` that carries survival from one milestone to the next,
+    /// starting from `S(0) = 1`. The final rate continues as an open-ended tail.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::EmptyIntervals`] when no milestones are supplied and
+    /// [`PiecewiseExponentialError::InvalidMilestone`] unless the times are finite and strictly
+    /// increasing from zero and the survivals lie in `(0, 1)` and strictly decrease.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let milestones = [(12.0, 0.4), (24.0, 0.2)];
+    /// let dist = PiecewiseExponential::from_survival_milestones(&milestones).unwrap();
+    /// assert!((dist.survival(12.0) - 0.4).abs() < 1e-12);
+    /// assert!((dist.survival(24.0) - 0.2).abs() < 1e-12);
+    /// ```
+    pub fn from_survival_milestones(
+        milestones: &[(f64, f64)],
+    ) -> Result<Self, PiecewiseExponentialError> {
+        if milestones.is_empty() {
+            return Err(PiecewiseExponentialError::EmptyIntervals);
+        }
+
+        let mut durations = Vec::with_capacity(milestones.len());
+        let mut rates = Vec::with_capacity(milestones.len());
+        let (mut previous_time, mut previous_survival) = (0.0, 1.0);
+        for (idx, &(time, survival)) in milestones.iter().enumerate() {
+            let valid_time = time.is_finite() && time > previous_time;
+            let valid_survival = survival > 0.0 && survival < previous_survival;
+            if !(valid_time && valid_survival) {
+                return Err(PiecewiseExponentialError::InvalidMilestone { index: idx });
+            }
+            let duration = time - previous_time;
+            durations.push(duration);
+            rates.push((math::ln(previous_survival) - math::ln(survival)) / duration);
+            (previous_time, previous_survival) = (time, survival);
+        }
+        *durations.last_mut().expect("milestones are non-empty") = f64::INFINITY;
+
+        Self::new(&durations, &rates)
+    }
+
+    /// Blend the hazards of `a` and `b` with a time-varying weight.
+    ///
+    /// On the union of both breakpoint grids each interval's rate is
+    /// `(1 - w) * rate_a + w * rate_b`, where `w` is `weight_fn` evaluated at the interval
+    /// midpoint and clamped to `[0, 1]`. An open-ended final interval has no midpoint, so its
+    /// weight is taken at its start. The blend ends where the later of the two final intervals
+    /// ends.
+    ///
+    /// When every weight is `0` the result is `a`, and when every weight is `1` it is `b`, each
+    /// returned unchanged including any [`truncate`](Self::truncate)d support. Any other mix of
+    /// a truncated input has no piecewise constant hazard and is rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::TruncatedHazard`] when a truncated input would be
+    /// mixed with the other, and [`PiecewiseExponentialError::NonFiniteRate`] when `weight_fn`
+    /// yields `NaN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+    /// let treated = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    /// // Treatment effect phases in linearly over the first three months.
+    /// let onset = PiecewiseExponential::new(&[1.0, 1.0, 1.0, f64::INFINITY], &[0.1; 4]).unwrap();
+    /// let blended = PiecewiseExponential::blend(&control, &onset, |t| t / 3.0).unwrap();
+    /// assert!((blended.hazard_at(0.0) - (5.0 / 6.0 * 0.2 + 1.0 / 6.0 * 0.1)).abs() < 1e-15);
+    /// assert_eq!(blended.hazard_at(3.0), treated.hazard_at(3.0));
+    /// ```
+    pub fn blend(
+        a: &PiecewiseExponential,
+        b: &PiecewiseExponential,
+        weight_fn: impl Fn(f64) -> f64,
+    ) -> Result<Self, PiecewiseExponentialError> {
+        let starts = union_breakpoints(a, b, f64::INFINITY);
+        let final_end = a.final_end.max(b.final_end);
+        let mut durations = Vec::with_capacity(starts.len());
+        let mut weights = Vec::with_capacity(starts.len());
+        for (idx, &start) in starts.iter().enumerate() {
+            let end = starts.get(idx + 1).copied().unwrap_or(final_end);
+            let at = if end.is_finite() {
+                0.5 * (start + end)
+            } else {
+                start
+            };
+            durations.push(end - start);
+            weights.push(weight_fn(at).clamp(0.0, 1.0));
+        }
+
+        if weights.iter().all(|&weight| weight == 0.0) {
+            return Ok(a.clone());
+        }
+        if weights.iter().all(|&weight| weight == 1.0) {
+            return Ok(b.clone());
+        }
+        if a.is_truncated() || b.is_truncated() {
+            return Err(PiecewiseExponentialError::TruncatedHazard);
+        }
+        let rates: Vec<f64> = starts
+            .iter()
+            .zip(&weights)
+            .map(|(&start, &weight)| {
+                (1.0 - weight) * a.base_hazard_at(start) + weight * b.base_hazard_at(start)
+            })
+            .collect();
+        Self::new(&durations, &rates)
+    }
+
+    /// Iterate over the intervals that define the distribution.
+    ///
+    /// The final interval ends at `f64::INFINITY` for an open-ended tail, or at its supplied
+    /// boundary otherwise. Sampling continues the final rate beyond a finite boundary.
+    ///
+    /// The rates parameterize the hazard before any [`truncate`](Self::truncate). A truncated
+    /// distribution ends its last interval at the truncation point, but its hazard
+    /// [`hazard_at`](Self::hazard_at) is not constant within an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let ends: Vec<f64> = dist.intervals().map(|interval| interval.end).collect();
+    /// assert_eq!(ends, [0.5, f64::INFINITY]);
+    /// ```
+    pub fn intervals(&self) -> impl Iterator<Item = Interval> + '_ {
+        let ends = self.cumulative_time[1..]
+            .iter()
+            .copied()
+            .chain(core::iter::once(self.final_end));
+        self.cumulative_time
+            .iter()
+            .zip(ends)
+            .zip(&self.rates)
+            .enumerate()
+            .map(|(index, ((&start, end), &rate))| Interval {
+                index,
+                start,
+                end,
+                rate,
+            })
+    }
+
+    /// Return the hazard as `(start, end, rate)` step segments for plotting.
+    ///
+    /// An open-ended tail is closed at the later of the 0.999 quantile and one mean tail duration
+    /// `1 / rate` past its start. Use [`hazard_steps_to`](Self::hazard_steps_to) to choose the
+    /// end explicitly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::TruncatedHazard`] for a
+    /// [`truncate`](Self::truncate)d distribution, whose hazard is not a step function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let steps = dist.hazard_steps().unwrap();
+    /// assert_eq!(steps[0], (0.0, 1.0, 0.5));
+    /// assert!(steps[1].1.is_finite());
+    /// ```
+    pub fn hazard_steps(&self) -> Result<Vec<(f64, f64, f64)>, PiecewiseExponentialError> {
+        if self.is_truncated() {
+            return Err(PiecewiseExponentialError::TruncatedHazard);
+        }
+        let last = self.rates.len() - 1;
+        let tail_start = self.cumulative_time[last];
+        let quantile = self.sample_from_hazard(self.hazard_from_uniform(0.001));
+        self.hazard_steps_to(quantile.max(tail_start + 1.0 / self.rates[last]))
+    }
+
+    /// Return the hazard as `(start, end, rate)` step segments, closing an open tail at
+    /// `tail_end`.
+    ///
+    /// A finite final boundary is kept as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::TruncatedHazard`] for a
+    /// [`truncate`](Self::truncate)d distribution, whose hazard is not a step function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let steps = dist.hazard_steps_to(36.0).unwrap();
+    /// assert_eq!(steps, [(0.0, 1.0, 0.5), (1.0, 36.0, 2.0)]);
+    /// ```
+    pub fn hazard_steps_to(
+        &self,
+        tail_end: f64,
+    ) -> Result<Vec<(f64, f64, f64)>, PiecewiseExponentialError> {
+        if self.is_truncated() {
+            return Err(PiecewiseExponentialError::TruncatedHazard);
+        }
+        Ok(self
+            .intervals()
+            .map(|interval| {
+                let end = if interval.end.is_infinite() {
+                    tail_end
+                } else {
+                    interval.end
+                };
+                (interval.start, end, interval.rate)
+            })
+            .collect())
+    }
+
+    /// Return whether the final interval is open-ended.
+    ///
+    /// This is `true` when the last duration was `f64::INFINITY` and `false` for a finite final
+    /// boundary or a [`truncate`](Self::truncate)d distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let open = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let finite = PiecewiseExponential::new(&[0.5, 1.0], &[1.0, 2.0]).unwrap();
+    /// assert!(open.has_open_tail());
+    /// assert!(!finite.has_open_tail());
+    /// ```
+    pub fn has_open_tail(&self) -> bool {
+        self.final_end.is_infinite()
+    }
+
+    /// Return whether the distribution was [`truncate`](Self::truncate)d.
+    ///
+    /// A truncated distribution has no piecewise constant hazard, so operations that rely on one,
+    /// such as [`hazard_steps`](Self::hazard_steps), reject it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.3]).unwrap();
+    /// assert!(!dist.is_truncated());
+    /// assert!(dist.truncate(4.0).unwrap().is_truncated());
+    /// ```
+    pub fn is_truncated(&self) -> bool {
+        self.support_end.is_finite()
+    }
+
+    /// Return the cumulative hazard at the start of each interval.
+    ///
+    /// The slice has one element per interval and starts at `0`, so pairing it with the interval
+    /// starts from [`intervals`](Self::intervals) traces the integrated hazard as a polyline. For
+    /// a [`truncate`](Self::truncate)d distribution the values are those before truncation,
+    /// matching the rates that [`intervals`](Self::intervals) reports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, 2.0, f64::INFINITY], &[0.5, 2.0, 1.0]).unwrap();
+    /// assert_eq!(dist.cumulative_hazard_breakpoints(), [0.0, 0.5, 4.5]);
+    /// ```
+    pub fn cumulative_hazard_breakpoints(&self) -> &[f64] {
+        &self.cumulative_hazard
+    }
+
     /// Draw a single sample from the distribution.
     ///
     /// The method accepts any [`rand::Rng`] implementation, so callers can use deterministic
@@ -148,8 +567,7 @@ impl PiecewiseExponential {
         R: Rng + ?Sized,
     {
         let uniform: f64 = rng.sample(Open01);
-        let hazard = -uniform.ln();
-        self.sample_from_hazard(hazard)
+        self.sample_from_hazard(self.hazard_from_uniform(uniform))
     }
 
     /// Transform a single uniform variate into a draw via the inverse cumulative distribution.
@@ -175,91 +593,1178 @@ impl PiecewiseExponential {
         if !(uniform > 0.0 && uniform <= 1.0) {
             return Err(PiecewiseExponentialSampleError::UniformOutOfRange { value: uniform });
         }
-        let hazard = -uniform.ln();
-        Ok(self.sample_from_hazard(hazard))
+        Ok(self.sample_from_hazard(self.hazard_from_uniform(uniform)))
     }
 
-    fn sample_from_hazard(&self, hazard: f64) -> f64 {
-        let idx = self
-            .cumulative_hazard
-            .partition_point(|&value| value <= hazard)
-            .saturating_sub(1);
-        let base_time = self.cumulative_time[idx];
-        let offset = (hazard - self.cumulative_hazard[idx]) / self.rates[idx];
-        base_time + offset
+    /// Compute the quantile `F^-1(p)` with explicit handling beyond a finite final boundary.
+    ///
+    /// When the final interval has a finite end, quantiles past it are governed by `mode`:
+    /// [`QuantileMode::Clamp`] returns the boundary, [`QuantileMode::Extrapolate`] continues the
+    /// final rate as sampling does, and [`QuantileMode::Error`] reports the overflow. Open-ended
+    /// and [`truncate`](Self::truncate)d distributions never exceed their support, so the mode
+    /// has no effect on them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialSampleError::ProbabilityOutOfRange`] unless `0 <= p < 1`, and
+    /// [`PiecewiseExponentialSampleError::BeyondFinalBoundary`] for a quantile past the final
+    /// boundary under [`QuantileMode::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::{PiecewiseExponential, QuantileMode};
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, 1.0], &[0.5, 0.5]).unwrap();
+    /// assert_eq!(dist.quantile_with(0.9, QuantileMode::Clamp).unwrap(), 2.0);
+    /// assert!(dist.quantile_with(0.9, QuantileMode::Extrapolate).unwrap() > 2.0);
+    /// assert!(dist.quantile_with(0.9, QuantileMode::Error).is_err());
+    /// ```
+    pub fn quantile_with(
+        &self,
+        p: f64,
+        mode: QuantileMode,
+    ) -> Result<f64, PiecewiseExponentialSampleError> {
+        if !(0.0..1.0).contains(&p) {
+            return Err(PiecewiseExponentialSampleError::ProbabilityOutOfRange { value: p });
+        }
+        let quantile = self.sample_from_hazard(-math::ln_1p(-p * self.truncation_mass()));
+        if quantile <= self.final_end {
+            return Ok(quantile);
+        }
+        match mode {
+            QuantileMode::Clamp => Ok(self.final_end),
+            QuantileMode::Extrapolate => Ok(quantile),
+            QuantileMode::Error => Err(PiecewiseExponentialSampleError::BeyondFinalBoundary {
+                probability: p,
+                boundary: self.final_end,
+            }),
+        }
     }
 
-    /// Draw `n` samples and return them as a `Vec<f64>`.
+    /// Return the inverse cumulative distribution as a plain `Fn(f64) -> f64` closure.
+    ///
+    /// Inputs are clamped into `(0, 1]` instead of producing an error: values at or below zero map
+    /// to the smallest positive uniform and values above one map to `1`. `NaN` propagates as
+    /// `NaN`. This suits generic Monte Carlo engines that expect an infallible quantile function.
     ///
     /// # Examples
     ///
     /// ```
-    /// use rand::rngs::StdRng;
-    /// use rand::SeedableRng;
     /// use simtrial::PiecewiseExponential;
     ///
-    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
-    /// let mut rng = StdRng::seed_from_u64(999);
-    /// let draws = dist.sample_n(4, &mut rng);
-    /// assert_eq!(draws.len(), 4);
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// let quantile = dist.quantile_fn();
+    /// assert_eq!(quantile(0.75), dist.inverse_cdf(0.75).unwrap());
+    /// assert_eq!(quantile(1.5), 0.0);
     /// ```
-    pub fn sample_n<R>(&self, n: usize, rng: &mut R) -> Vec<f64>
-    where
-        R: Rng + ?Sized,
-    {
-        (0..n).map(|_| self.sample(rng)).collect()
+    pub fn quantile_fn(&self) -> impl Fn(f64) -> f64 + '_ {
+        move |uniform| {
+            let clamped = uniform.clamp(f64::MIN_POSITIVE, 1.0);
+            self.sample_from_hazard(self.hazard_from_uniform(clamped))
+        }
     }
-}
 
-/// Errors emitted when constructing a [`PiecewiseExponential`] from invalid parameters.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum PiecewiseExponentialError {
-    /// No intervals were supplied.
-    EmptyIntervals,
-    /// Durations and rates have mismatched lengths.
-    LengthMismatch {
-        /// Number of durations supplied.
-        durations: usize,
-        /// Number of rates supplied.
-        rates: usize,
-    },
-    /// Encountered a non-finite duration outside the final interval.
-    NonFiniteDuration {
-        /// Index of the offending duration.
-        index: usize,
-    },
-    /// Encountered a non-positive duration outside the final interval.
-    NonPositiveDuration {
-        /// Index of the offending duration.
-        index: usize,
-    },
-    /// The last interval duration is not strictly positive.
-    NonPositiveFinalDuration,
-    /// The last interval duration is not finite nor positive infinity.
-    FinalDurationInvalid,
-    /// Encountered a non-finite rate.
-    NonFiniteRate {
-        /// Index of the offending rate.
-        index: usize,
-    },
-    /// Encountered a non-positive rate.
-    NonPositiveRate {
-        /// Index of the offending rate.
-        index: usize,
-    },
-}
+    /// Check that [`inverse_cdf`](Self::inverse_cdf) is monotone over the supplied uniforms.
+    ///
+    /// The uniforms are sorted before evaluation. Because a uniform plays the role of a survival
+    /// probability, larger uniforms must map to earlier (or equal) times. Returns `false` when any
+    /// uniform falls outside `(0, 1]` or the ordering is violated, which flags interval-lookup
+    /// regressions near breakpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// assert!(dist.inverse_cdf_is_monotone(&[0.9, 0.1, 0.5]));
+    /// assert!(!dist.inverse_cdf_is_monotone(&[0.5, 0.0]));
+    /// ```
+    pub fn inverse_cdf_is_monotone(&self, us: &[f64]) -> bool {
+        let mut sorted = us.to_vec();
+        sorted.sort_by(f64::total_cmp);
 
-impl fmt::Display for PiecewiseExponentialError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            PiecewiseExponentialError::EmptyIntervals => {
-                f.write_str("durations must contain at least one interval")
+        let mut previous = f64::INFINITY;
+        for &uniform in &sorted {
+            match self.inverse_cdf(uniform) {
+                Ok(time) if time <= previous => previous = time,
+                _ => return false,
             }
-            PiecewiseExponentialError::LengthMismatch { durations, rates } => write!(
-                f,
-                "durations and rates must have the same length ({} vs {})",
-                durations, rates
-            ),
+        }
+        true
+    }
+
+    /// Evaluate the cumulative hazard `H(t)` at time `t`.
+    ///
+    /// Times at or below zero return `0`, and the final rate extends beyond the last breakpoint.
+    /// For a [`truncate`](Self::truncate)d distribution the cumulative hazard is infinite from the
+    /// truncation point onwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// assert_eq!(dist.cumulative_hazard_at(1.0), 0.5);
+    /// assert_eq!(dist.cumulative_hazard_at(2.0), 2.5);
+    /// ```
+    pub fn cumulative_hazard_at(&self, t: f64) -> f64 {
+        if !self.is_truncated() || t <= 0.0 {
+            return self.base_cumulative_hazard_at(t);
+        }
+        if t >= self.support_end {
+            return f64::INFINITY;
+        }
+        // -ln((S(t) - S(end)) / (1 - S(end))), factored to avoid cancellation near `end`.
+        let remaining =
+            self.base_cumulative_hazard_at(self.support_end) - self.base_cumulative_hazard_at(t);
+        self.base_cumulative_hazard_at(t) - math::ln(-math::exp_m1(-remaining))
+            + math::ln(self.truncation_mass())
+    }
+
+    pub(crate) fn base_cumulative_hazard_at(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        let idx = self
+            .cumulative_time
+            .partition_point(|&value| value <= t)
+            .saturating_sub(1);
+        self.cumulative_hazard[idx] + (t - self.cumulative_time[idx]) * self.rates[idx]
+    }
+
+    /// Evaluate the survival function `S(t) = exp(-H(t))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// assert_eq!(dist.survival(0.0), 1.0);
+    /// assert!((dist.survival(0.5) - (-1.0_f64).exp()).abs() < 1e-15);
+    /// ```
+    pub fn survival(&self, t: f64) -> f64 {
+        math::exp(-self.cumulative_hazard_at(t))
+    }
+
+    /// Evaluate the conditional survival `S(t | T > s) = S(t) / S(s)`.
+    ///
+    /// The ratio is computed as `exp(-(H(t) - H(s)))` to stay accurate deep in the tail. Times
+    /// `t <= s` return `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// assert!((dist.conditional_survival(1.5, 1.0) - (-1.0_f64).exp()).abs() < 1e-15);
+    /// assert_eq!(dist.conditional_survival(0.5, 1.0), 1.0);
+    /// ```
+    pub fn conditional_survival(&self, t: f64, s: f64) -> f64 {
+        if t <= s {
+            return 1.0;
+        }
+        math::exp(-(self.cumulative_hazard_at(t) - self.cumulative_hazard_at(s)))
+    }
+
+    /// Compute the probability that none of `n` independent patients has an event by time `t`.
+    ///
+    /// The value `S(t)^n` is evaluated as `exp(-n * H(t))`, which avoids overflowing an integer
+    /// exponent and stays accurate for large cohorts. With `n = 0` the result is `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.01]).unwrap();
+    /// let probability = dist.prob_no_events(100, 1.0);
+    /// assert!((probability - (-1.0_f64).exp()).abs() < 1e-15);
+    /// ```
+    pub fn prob_no_events(&self, n: usize, t: f64) -> f64 {
+        if n == 0 {
+            return 1.0;
+        }
+        math::exp(-(n as f64) * self.cumulative_hazard_at(t))
+    }
+
+    /// Evaluate the cumulative distribution function `F(t) = 1 - S(t)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// assert_eq!(dist.cdf(0.0), 0.0);
+    /// assert!((dist.cdf(0.5) - (1.0 - (-1.0_f64).exp())).abs() < 1e-15);
+    /// ```
+    pub fn cdf(&self, t: f64) -> f64 {
+        if t >= self.support_end {
+            return 1.0;
+        }
+        -math::exp_m1(-self.base_cumulative_hazard_at(t)) / self.truncation_mass()
+    }
+
+    /// Evaluate the hazard rate `h(t)`.
+    ///
+    /// The hazard is right-continuous, so a breakpoint takes the rate of the interval it opens.
+    /// Negative times return `0`. A [`truncate`](Self::truncate)d distribution has an increasing
+    /// hazard within each interval that becomes infinite at the truncation point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// assert_eq!(dist.hazard_at(0.5), 0.5);
+    /// assert_eq!(dist.hazard_at(1.0), 2.0);
+    /// ```
+    pub fn hazard_at(&self, t: f64) -> f64 {
+        if t < 0.0 {
+            return 0.0;
+        }
+        if self.is_truncated() {
+            if t >= self.support_end {
+                return f64::INFINITY;
+            }
+            let remaining = self.base_cumulative_hazard_at(self.support_end)
+                - self.base_cumulative_hazard_at(t);
+            return self.base_hazard_at(t) / -math::exp_m1(-remaining);
+        }
+        self.base_hazard_at(t)
+    }
+
+    pub(crate) fn base_hazard_at(&self, t: f64) -> f64 {
+        let idx = self
+            .cumulative_time
+            .partition_point(|&value| value <= t)
+            .saturating_sub(1);
+        self.rates[idx]
+    }
+
+    /// Evaluate the probability density `f(t) = h(t) S(t)`.
+    ///
+    /// A [`truncate`](Self::truncate)d distribution has zero density beyond the truncation point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// assert_eq!(dist.density(0.0), 2.0);
+    /// assert_eq!(dist.density(-1.0), 0.0);
+    /// ```
+    pub fn density(&self, t: f64) -> f64 {
+        if t < 0.0 || t > self.support_end {
+            return 0.0;
+        }
+        self.base_hazard_at(t) * math::exp(-self.base_cumulative_hazard_at(t))
+            / self.truncation_mass()
+    }
+
+    /// Compute the importance weight `other.density(t) / self.density(t)` for a draw `t` from
+    /// `self`.
+    ///
+    /// Here `self` is the proposal and `other` is the target, so averaging `g(t) * weight` over
+    /// draws from `self` estimates the expectation of `g` under `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let proposal = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    /// let target = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// let weight = proposal.importance_weight(&target, 0.0);
+    /// assert!((weight - 4.0).abs() < 1e-12);
+    /// ```
+    pub fn importance_weight(&self, other: &Self, t: f64) -> f64 {
+        if t > other.support_end {
+            return 0.0;
+        }
+        // The ratio of densities, evaluated in log space for stability deep in the tail.
+        let log_ratio = self.base_cumulative_hazard_at(t) - other.base_cumulative_hazard_at(t);
+        other.base_hazard_at(t) / self.base_hazard_at(t)
+            * math::exp(log_ratio)
+            * (self.truncation_mass() / other.truncation_mass())
+    }
+
+    /// Compute the restricted mean survival time `∫_0^tau S(t) dt`.
+    ///
+    /// The integral is evaluated in closed form interval by interval. Non-positive `tau` returns
+    /// `0`, and `tau = f64::INFINITY` yields the mean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    /// let expected = (1.0 - (-1.0_f64).exp()) / 0.5;
+    /// assert!((dist.rmst(2.0) - expected).abs() < 1e-12);
+    /// ```
+    pub fn rmst(&self, tau: f64) -> f64 {
+        if !self.is_truncated() {
+            return self.base_rmst(tau);
+        }
+        let upper = tau.min(self.support_end);
+        if upper <= 0.0 {
+            return 0.0;
+        }
+        let floor = math::exp(-self.base_cumulative_hazard_at(self.support_end));
+        (self.base_rmst(upper) - upper * floor) / self.truncation_mass()
+    }
+
+    /// Integrate `S(t)` over `[start, end]`, a segment that contains no interval start in its
+    /// interior.
+    pub(crate) fn rmst_over_segment(&self, start: f64, end: f64) -> f64 {
+        let end = end.min(self.support_end);
+        if end <= start {
+            return 0.0;
+        }
+        let rate = self.base_hazard_at(start);
+        let base = math::exp(-self.base_cumulative_hazard_at(start))
+            * -math::exp_m1(-rate * (end - start))
+            / rate;
+        if !self.is_truncated() {
+            return base;
+        }
+        let floor = math::exp(-self.base_cumulative_hazard_at(self.support_end));
+        (base - (end - start) * floor) / self.truncation_mass()
+    }
+
+    fn base_rmst(&self, tau: f64) -> f64 {
+        let mut total = 0.0;
+        for (idx, (&start, &rate)) in self.cumulative_time.iter().zip(&self.rates).enumerate() {
+            if start >= tau {
+                break;
+            }
+            let end = self
+                .cumulative_time
+                .get(idx + 1)
+                .map_or(tau, |&next| next.min(tau));
+            let survival_at_start = math::exp(-self.cumulative_hazard[idx]);
+            total += survival_at_start * -math::exp_m1(-rate * (end - start)) / rate;
+        }
+        total
+    }
+
+    /// Compute the mean `E[T]`.
+    ///
+    /// The final rate extends indefinitely, so the mean is always finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.25]).unwrap();
+    /// assert_eq!(dist.mean(), 4.0);
+    /// ```
+    pub fn mean(&self) -> f64 {
+        self.rmst(f64::INFINITY)
+    }
+
+    /// Compute the variance `Var[T]`.
+    ///
+    /// The second moment `2 ∫_0^∞ t S(t) dt` is evaluated in closed form on each interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    /// assert_eq!(dist.variance(), 4.0);
+    /// ```
+    pub fn variance(&self) -> f64 {
+        let mut second_moment = 0.0;
+        for (idx, (&start, &rate)) in self.cumulative_time.iter().zip(&self.rates).enumerate() {
+            let survival_at_start = math::exp(-self.cumulative_hazard[idx]);
+            // ∫_a^b t exp(-rate (t - a)) dt, split into the `a` term and the offset term.
+            let end = self
+                .cumulative_time
+                .get(idx + 1)
+                .copied()
+                .unwrap_or(self.support_end);
+            let (mass, offset_term) = if end.is_finite() {
+                let exposure = rate * (end - start);
+                let decay = math::exp(-exposure);
+                (
+                    -math::exp_m1(-exposure),
+                    -math::exp_m1(-exposure) - exposure * decay,
+                )
+            } else {
+                (1.0, 1.0)
+            };
+            second_moment +=
+                2.0 * survival_at_start * (start * mass / rate + offset_term / (rate * rate));
+        }
+        if self.is_truncated() {
+            let end = self.support_end;
+            let floor = math::exp(-self.base_cumulative_hazard_at(end));
+            second_moment = (second_moment - end * end * floor) / self.truncation_mass();
+        }
+        let mean = self.mean();
+        second_moment - mean * mean
+    }
+
+    /// Compute the coefficient of variation `sqrt(Var[T]) / E[T]`.
+    ///
+    /// Returns `None` when the moments are not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// assert_eq!(dist.coefficient_of_variation(), Some(1.0));
+    /// ```
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        let ratio = math::sqrt(self.variance()) / self.mean();
+        ratio.is_finite().then_some(ratio)
+    }
+
+    /// Render the survival curve over `[0, tau]` as a block-character sparkline.
+    ///
+    /// The curve is evaluated at `width` evenly spaced points including both end points, and each
+    /// survival probability is mapped onto the eight levels `▁▂▃▄▅▆▇█`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[1.0]).unwrap();
+    /// let line = dist.survival_sparkline(3.0, 8);
+    /// assert_eq!(line.chars().count(), 8);
+    /// assert!(line.starts_with('█'));
+    /// ```
+    pub fn survival_sparkline(&self, tau: f64, width: usize) -> String {
+        const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let step = if width > 1 {
+            tau / (width - 1) as f64
+        } else {
+            0.0
+        };
+        (0..width)
+            .map(|idx| {
+                let survival = self.survival(idx as f64 * step);
+                let level = math::round(survival * (LEVELS.len() - 1) as f64) as usize;
+                LEVELS[level.min(LEVELS.len() - 1)]
+            })
+            .collect()
+    }
+
+    /// Classify the shape of the hazard from the sequence of interval rates.
+    ///
+    /// Repeated adjacent rates are ignored. A sequence that only falls and then only rises is a
+    /// [`HazardShape::Bathtub`]; any other change of direction is [`HazardShape::Mixed`]. The
+    /// hazard of a [`truncate`](Self::truncate)d distribution rises within every interval, so it
+    /// is [`HazardShape::Increasing`] unless a rate falls, which makes it mixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::{HazardShape, PiecewiseExponential};
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, 4.0, f64::INFINITY], &[0.5, 0.1, 0.4]).unwrap();
+    /// assert_eq!(dist.hazard_shape(), HazardShape::Bathtub);
+    /// ```
+    pub fn hazard_shape(&self) -> HazardShape {
+        let mut rising = self.is_truncated();
+        let mut falling = false;
+        let mut bathtub = true;
+        for pair in self.rates.windows(2) {
+            if pair[1] > pair[0] {
+                rising = true;
+            } else if pair[1] < pair[0] {
+                // A fall after a rise rules out a bathtub.
+                bathtub &= !rising;
+                falling = true;
+            }
+        }
+        match (rising, falling) {
+            (false, false) => HazardShape::Constant,
+            (true, false) => HazardShape::Increasing,
+            (false, true) => HazardShape::Decreasing,
+            (true, true) if bathtub => HazardShape::Bathtub,
+            (true, true) => HazardShape::Mixed,
+        }
+    }
+
+    /// Check whether two distributions define the same hazard, ignoring how it is split.
+    ///
+    /// Rates are compared within `tol` on the union of both breakpoint grids, so adjacent
+    /// intervals that share a rate match a single coalesced interval. Truncation points must also
+    /// agree within `tol`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let coarse = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let split = PiecewiseExponential::new(&[0.4, 0.6, f64::INFINITY], &[0.5, 0.5, 2.0]);
+    /// assert!(coarse.statistically_eq(&split.unwrap(), 1e-12));
+    /// ```
+    pub fn statistically_eq(&self, other: &Self, tol: f64) -> bool {
+        let same_support = self.support_end == other.support_end
+            || (self.support_end - other.support_end).abs() <= tol;
+        if !same_support {
+            return false;
+        }
+        let mut grid = self.cumulative_time.clone();
+        grid.extend_from_slice(&other.cumulative_time);
+        grid.sort_by(f64::total_cmp);
+        grid.dedup();
+        grid.iter()
+            .all(|&t| (self.base_hazard_at(t) - other.base_hazard_at(t)).abs() <= tol)
+    }
+
+    /// Compute the per-interval hazard ratio of `other` relative to `self`.
+    ///
+    /// Each element is `other.rate[i] / self.rate[i]`, treating `self` as the reference arm. Both
+    /// distributions must share the same breakpoints.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::TruncatedHazard`] when either distribution is
+    /// [`truncate`](Self::truncate)d, and [`PiecewiseExponentialError::BreakpointMismatch`] when
+    /// the breakpoints differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let control = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.1]).unwrap();
+    /// let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.06]).unwrap();
+    /// let ratios = control.hazard_ratio_by_interval(&experimental).unwrap();
+    /// assert_eq!(ratios.len(), 2);
+    /// assert!((ratios[1] - 0.6).abs() < 1e-12);
+    /// ```
+    pub fn hazard_ratio_by_interval(
+        &self,
+        other: &Self,
+    ) -> Result<Vec<f64>, PiecewiseExponentialError> {
+        if self.is_truncated() || other.is_truncated() {
+            return Err(PiecewiseExponentialError::TruncatedHazard);
+        }
+        if self.cumulative_time != other.cumulative_time {
+            return Err(PiecewiseExponentialError::BreakpointMismatch);
+        }
+        Ok(self
+            .rates
+            .iter()
+            .zip(&other.rates)
+            .map(|(&reference, &rate)| rate / reference)
+            .collect())
+    }
+
+    /// Compute a chi-square uniformity statistic for the sample-then-CDF pipeline.
+    ///
+    /// Draws `n` samples from a generator seeded with `seed`, maps each through
+    /// [`cdf`](Self::cdf), and compares the counts in `bins` equal-width bins on `[0, 1)` with
+    /// the uniform expectation `n / bins`. Under a correct implementation the statistic follows
+    /// a chi-square distribution with `bins - 1` degrees of freedom. Returns `NaN` when `n` or
+    /// `bins` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "diagnostics")]
+    /// # {
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let statistic = dist.chi_square_uniformity(10_000, 10, 1);
+    /// assert!(statistic < 27.88); // 0.999 quantile with 9 degrees of freedom.
+    /// # }
+    /// ```
+    #[cfg(feature = "diagnostics")]
+    pub fn chi_square_uniformity(&self, n: usize, bins: usize, seed: u64) -> f64 {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        if n == 0 || bins == 0 {
+            return f64::NAN;
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut counts = alloc::vec![0_usize; bins];
+        for _ in 0..n {
+            let probability = self.cdf(self.sample(&mut rng));
+            let bin = (probability * bins as f64) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+
+        let expected = n as f64 / bins as f64;
+        counts
+            .iter()
+            .map(|&count| {
+                let deviation = count as f64 - expected;
+                deviation * deviation / expected
+            })
+            .sum()
+    }
+
+    /// Compute the expected number of events in each interval for the supplied follow-up times.
+    ///
+    /// Each element is `rate[i]` multiplied by the total time the cohort spends at risk in
+    /// interval `i`, the Poisson expectation under the model. Comparing it with the observed
+    /// events per interval gives a residual diagnostic. Follow-up beyond the last breakpoint
+    /// accrues to the final interval. For a [`truncate`](Self::truncate)d distribution the rate
+    /// is not constant, and the increase of [`cumulative_hazard_at`](Self::cumulative_hazard_at)
+    /// over each exposure is used instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let predicted = dist.predicted_events_by_interval(&[0.5, 3.0]);
+    /// assert_eq!(predicted, [0.75, 4.0]);
+    /// ```
+    pub fn predicted_events_by_interval(&self, times: &[f64]) -> Vec<f64> {
+        let mut predicted = alloc::vec![0.0; self.rates.len()];
+        for &time in times {
+            for (idx, (&start, &rate)) in self.cumulative_time.iter().zip(&self.rates).enumerate() {
+                if start >= time {
+                    break;
+                }
+                let end = self
+                    .cumulative_time
+                    .get(idx + 1)
+                    .map_or(time, |&next| next.min(time));
+                predicted[idx] += if self.is_truncated() {
+                    self.cumulative_hazard_at(end) - self.cumulative_hazard_at(start)
+                } else {
+                    rate * (end - start)
+                };
+            }
+        }
+        predicted
+    }
+
+    /// Compute the Cox-Snell residual `H(t)` for each observation, paired with its event indicator.
+    ///
+    /// When the model fits, the residuals behave like a censored sample from a unit exponential,
+    /// with `events` as the censoring indicators. A Kaplan-Meier curve of the returned pairs that
+    /// tracks `exp(-r)` indicates an adequate fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialError::ObservationLengthMismatch`] when `times` and `events`
+    /// have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let residuals = dist.cox_snell_residuals(&[1.0, 2.0], &[true, false]).unwrap();
+    /// assert_eq!(residuals, [(0.5, true), (2.5, false)]);
+    /// ```
+    pub fn cox_snell_residuals(
+        &self,
+        times: &[f64],
+        events: &[bool],
+    ) -> Result<Vec<(f64, bool)>, PiecewiseExponentialError> {
+        if times.len() != events.len() {
+            return Err(PiecewiseExponentialError::ObservationLengthMismatch {
+                times: times.len(),
+                events: events.len(),
+            });
+        }
+        Ok(times
+            .iter()
+            .zip(events)
+            .map(|(&time, &event)| (self.cumulative_hazard_at(time), event))
+            .collect())
+    }
+
+    /// End of the support, `f64::INFINITY` unless truncated.
+    pub(crate) fn support_end(&self) -> f64 {
+        self.support_end
+    }
+
+    /// Probability mass of the untruncated distribution within the support, `1 - S(end)`.
+    pub(crate) fn truncation_mass(&self) -> f64 {
+        if self.is_truncated() {
+            -math::exp_m1(-self.base_cumulative_hazard_at(self.support_end))
+        } else {
+            1.0
+        }
+    }
+
+    /// Map a survival-scale uniform to the cumulative hazard of the untruncated distribution.
+    fn hazard_from_uniform(&self, uniform: f64) -> f64 {
+        if self.is_truncated() {
+            -math::ln_1p(-(1.0 - uniform) * self.truncation_mass())
+        } else {
+            -math::ln(uniform)
+        }
+    }
+
+    fn sample_from_hazard(&self, hazard: f64) -> f64 {
+        let idx = self
+            .cumulative_hazard
+            .partition_point(|&value| value <= hazard)
+            .saturating_sub(1);
+        let base_time = self.cumulative_time[idx];
+        let offset = (hazard - self.cumulative_hazard[idx]) / self.rates[idx];
+        base_time + offset
+    }
+
+    /// Draw `n` samples and return them as a `Vec<f64>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(999);
+    /// let draws = dist.sample_n(4, &mut rng);
+    /// assert_eq!(draws.len(), 4);
+    /// ```
+    pub fn sample_n<R>(&self, n: usize, rng: &mut R) -> Vec<f64>
+    where
+        R: Rng + ?Sized,
+    {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+
+    /// Draw `n` samples and return each as a `(uniform, time)` pair.
+    ///
+    /// The uniform is the exact variate consumed from `rng`, so the stream can be logged and
+    /// diffed against another implementation. Passing it to [`inverse_cdf`](Self::inverse_cdf)
+    /// reproduces the time, and the times match [`sample_n`](Self::sample_n) for the same seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(999);
+    /// for (uniform, time) in dist.sample_n_with_uniforms(4, &mut rng) {
+    ///     assert_eq!(dist.inverse_cdf(uniform).unwrap(), time);
+    /// }
+    /// ```
+    pub fn sample_n_with_uniforms<R>(&self, n: usize, rng: &mut R) -> Vec<(f64, f64)>
+    where
+        R: Rng + ?Sized,
+    {
+        (0..n)
+            .map(|_| {
+                let uniform: f64 = rng.sample(Open01);
+                (
+                    uniform,
+                    self.sample_from_hazard(self.hazard_from_uniform(uniform)),
+                )
+            })
+            .collect()
+    }
+
+    /// Map the first `n` points of the Halton sequence in `base` through the inverse CDF.
+    ///
+    /// The `i`-th point, for `i` in `1..=n`, is the radical inverse of `i` in `base`, so the
+    /// points lie in `(0, 1)` and fill it evenly. The result is a deterministic low-discrepancy
+    /// sample whose averages typically converge faster than those of pseudo-random draws.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialSampleError::InvalidHaltonBase`] when `base < 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    /// let draws = dist.sample_halton(3, 2).unwrap();
+    /// assert_eq!(draws[0], dist.inverse_cdf(0.5).unwrap());
+    /// assert_eq!(draws[1], dist.inverse_cdf(0.25).unwrap());
+    /// ```
+    pub fn sample_halton(
+        &self,
+        n: usize,
+        base: usize,
+    ) -> Result<Vec<f64>, PiecewiseExponentialSampleError> {
+        if base < 2 {
+            return Err(PiecewiseExponentialSampleError::InvalidHaltonBase { base });
+        }
+        Ok((1..=n)
+            .map(|index| {
+                let uniform = radical_inverse(index, base);
+                self.sample_from_hazard(self.hazard_from_uniform(uniform))
+            })
+            .collect())
+    }
+
+    /// Draw the total of `m` independent samples without allocating the individual draws.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[0.5, f64::INFINITY], &[1.0, 2.0]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(999);
+    /// let total = dist.sample_total(4, &mut rng);
+    /// assert!(total >= 0.0);
+    /// ```
+    pub fn sample_total<R>(&self, m: usize, rng: &mut R) -> f64
+    where
+        R: Rng + ?Sized,
+    {
+        (0..m).map(|_| self.sample(rng)).sum()
+    }
+
+    /// Draw an `(intermediate, terminal)` pair of event times with `intermediate <= terminal`.
+    ///
+    /// The draw follows an illness-death model where `self` gives the hazard of the intermediate
+    /// event (such as progression) and `terminal` the hazard of the terminal event (such as
+    /// death), which applies both before and after the intermediate event. A terminal time is
+    /// drawn after an intermediate time; when the terminal event comes first it also ends the
+    /// intermediate endpoint, as with progression-free and overall survival.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let progression = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+    /// let death = PiecewiseExponential::new(&[1.0], &[0.05]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(36);
+    /// let (pfs, os) = progression.sample_semicompeting(&death, &mut rng);
+    /// assert!(pfs <= os);
+    /// ```
+    pub fn sample_semicompeting<R>(&self, terminal: &Self, rng: &mut R) -> (f64, f64)
+    where
+        R: Rng + ?Sized,
+    {
+        let intermediate = self.sample(rng);
+        // The terminal hazard does not change at the intermediate event, so one terminal draw
+        // serves both before and after it.
+        let terminal = terminal.sample(rng);
+        (intermediate.min(terminal), terminal)
+    }
+
+    /// Draw a sample whose hazard is multiplied by a gamma frailty with mean one.
+    ///
+    /// The frailty `z` follows a gamma distribution with shape `1 / frailty_variance` and scale
+    /// `frailty_variance`, and the hazard drawn from the uniform is divided by `z` before
+    /// inversion. The marginal survival is `(1 + frailty_variance * H(t))^(-1 / frailty_variance)`.
+    /// A zero variance reduces to [`sample`](Self::sample).
+    ///
+    /// For a [`truncate`](Self::truncate)d distribution the frailty scales the untruncated
+    /// hazard, and each draw solves `S(t)^z = 1 - (1 - u) * (1 - S(t_max)^z)` so it stays within
+    /// the support.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialSampleError::InvalidFrailtyVariance`] when
+    /// `frailty_variance` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(39);
+    /// let draw = dist.sample_with_frailty(0.5, &mut rng).unwrap();
+    /// assert!(draw >= 0.0);
+    /// ```
+    pub fn sample_with_frailty<R>(
+        &self,
+        frailty_variance: f64,
+        rng: &mut R,
+    ) -> Result<f64, PiecewiseExponentialSampleError>
+    where
+        R: Rng + ?Sized,
+    {
+        if !(frailty_variance.is_finite() && frailty_variance >= 0.0) {
+            return Err(PiecewiseExponentialSampleError::InvalidFrailtyVariance {
+                variance: frailty_variance,
+            });
+        }
+        if frailty_variance == 0.0 {
+            return Ok(self.sample(rng));
+        }
+        let gamma = Gamma::new(1.0 / frailty_variance, frailty_variance)
+            .expect("frailty shape and scale are positive and finite");
+        let frailty: f64 = gamma.sample(rng);
+        let uniform: f64 = rng.sample(Open01);
+        if !self.is_truncated() {
+            return Ok(self.sample_from_hazard(-math::ln(uniform) / frailty));
+        }
+        let support_hazard = self.base_cumulative_hazard_at(self.support_end);
+        let mass = -math::exp_m1(-frailty * support_hazard);
+        let hazard = if mass > 0.0 {
+            -math::ln_1p(-(1.0 - uniform) * mass) / frailty
+        } else {
+            // A vanishing frailty spreads the truncated mass evenly over the cumulative hazard.
+            (1.0 - uniform) * support_hazard
+        };
+        Ok(self.sample_from_hazard(hazard).min(self.support_end))
+    }
+
+    /// Draw the `k`-th smallest of `m` independent samples without generating all `m` draws.
+    ///
+    /// The `k`-th uniform order statistic follows a `Beta(k, m - k + 1)` distribution, so a single
+    /// beta variate is pushed through the inverse cumulative distribution instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialSampleError::OrderStatisticOutOfRange`] unless
+    /// `1 <= k <= m`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 1.0]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(11);
+    /// let minimum = dist.sample_order_statistic(100, 1, &mut rng).unwrap();
+    /// assert!(minimum >= 0.0);
+    /// ```
+    pub fn sample_order_statistic<R>(
+        &self,
+        m: usize,
+        k: usize,
+        rng: &mut R,
+    ) -> Result<f64, PiecewiseExponentialSampleError>
+    where
+        R: Rng + ?Sized,
+    {
+        if k == 0 || k > m {
+            return Err(PiecewiseExponentialSampleError::OrderStatisticOutOfRange { k, m });
+        }
+        // Survival uniforms run opposite to time, so the k-th smallest time corresponds to the
+        // k-th largest survival uniform, which is Beta(m - k + 1, k).
+        let beta = Beta::new((m - k + 1) as f64, k as f64)
+            .expect("order statistic shape parameters are positive and finite");
+        let uniform: f64 = beta.sample(rng);
+        Ok(self.sample_from_hazard(self.hazard_from_uniform(uniform)))
+    }
+
+    /// Draw a sample conditional on `t0 < T <= t_max`.
+    ///
+    /// The draw uses the doubly truncated inverse transform, so delayed entry at `t0` and
+    /// administrative censoring at `t_max` are handled in a single uniform. `t_max` may be
+    /// `f64::INFINITY` to express left truncation only.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseExponentialSampleError::EmptyWindow`] unless `0 <= t0 < t_max`, when
+    /// `t0` is at or beyond the end of a [`truncate`](Self::truncate)d support, and when the
+    /// window is too narrow for its conditional mass or a draw strictly after `t0` to be
+    /// represented in floating point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use simtrial::PiecewiseExponential;
+    ///
+    /// let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    /// let mut rng = StdRng::seed_from_u64(21);
+    /// let draw = dist.sample_window(0.5, 1.5, &mut rng).unwrap();
+    /// assert!(draw > 0.5 && draw <= 1.5);
+    /// ```
+    pub fn sample_window<R>(
+        &self,
+        t0: f64,
+        t_max: f64,
+        rng: &mut R,
+    ) -> Result<f64, PiecewiseExponentialSampleError>
+    where
+        R: Rng + ?Sized,
+    {
+        let empty = PiecewiseExponentialSampleError::EmptyWindow {
+            lower: t0,
+            upper: t_max,
+        };
+        if !(t0 >= 0.0 && t_max > t0) || t0 >= self.support_end {
+            return Err(empty);
+        }
+        // Inside the support, truncation only rescales the mass, so it cancels on conditioning.
+        let lower_hazard = self.base_cumulative_hazard_at(t0);
+        let window_hazard =
+            self.base_cumulative_hazard_at(t_max.min(self.support_end)) - lower_hazard;
+        // Conditional probability of failing inside the window given survival to `t0`.
+        let mass = -math::exp_m1(-window_hazard);
+        if mass <= 0.0 {
+            return Err(empty);
+        }
+
+        let uniform: f64 = rng.sample(Open01);
+        let hazard = lower_hazard - math::ln_1p(-uniform * mass);
+        let draw = self.sample_from_hazard(hazard).min(t_max);
+        if draw <= t0 {
+            return Err(empty);
+        }
+        Ok(draw)
+    }
+}
+
+/// Reflect the base-`base` digits of `index` about the radix point.
+fn radical_inverse(mut index: usize, base: usize) -> f64 {
+    let inverse_base = 1.0 / base as f64;
+    let mut scale = inverse_base;
+    let mut value = 0.0;
+    while index > 0 {
+        value += (index % base) as f64 * scale;
+        index /= base;
+        scale *= inverse_base;
+    }
+    value
+}
+
+/// A single constant-hazard interval of a [`PiecewiseExponential`] distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    /// Zero-based position of the interval.
+    pub index: usize,
+    /// Time at which the interval starts.
+    pub start: f64,
+    /// Time at which the interval ends, `f64::INFINITY` for an open-ended tail.
+    pub end: f64,
+    /// Hazard rate within the interval.
+    pub rate: f64,
+}
+
+/// How [`PiecewiseExponential::quantile_with`] treats quantiles beyond a finite final boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantileMode {
+    /// Return the final boundary.
+    Clamp,
+    /// Continue the final rate past the boundary, matching sampling.
+    Extrapolate,
+    /// Report [`PiecewiseExponentialSampleError::BeyondFinalBoundary`].
+    Error,
+}
+
+/// Shape of the hazard of a [`PiecewiseExponential`] distribution across its intervals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardShape {
+    /// Every interval shares the same rate.
+    Constant,
+    /// Rates never fall and rise at least once.
+    Increasing,
+    /// Rates never rise and fall at least once.
+    Decreasing,
+    /// Rates fall and then rise, with no fall after the first rise.
+    Bathtub,
+    /// Rates change direction in any other pattern.
+    Mixed,
+}
+
+/// Errors emitted when constructing a [`PiecewiseExponential`] from invalid parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PiecewiseExponentialError {
+    /// No intervals were supplied.
+    EmptyIntervals,
+    /// Durations and rates have mismatched lengths.
+    LengthMismatch {
+        /// Number of durations supplied.
+        durations: usize,
+        /// Number of rates supplied.
+        rates: usize,
+    },
+    /// Encountered a non-finite duration outside the final interval.
+    NonFiniteDuration {
+        /// Index of the offending duration.
+        index: usize,
+    },
+    /// Encountered a non-positive duration outside the final interval.
+    NonPositiveDuration {
+        /// Index of the offending duration.
+        index: usize,
+    },
+    /// The last interval duration is not strictly positive.
+    NonPositiveFinalDuration,
+    /// The last interval duration is not finite nor positive infinity.
+    FinalDurationInvalid,
+    /// Encountered a non-finite rate.
+    NonFiniteRate {
+        /// Index of the offending rate.
+        index: usize,
+    },
+    /// Encountered a non-positive rate.
+    NonPositiveRate {
+        /// Index of the offending rate.
+        index: usize,
+    },
+    /// Two distributions that must share breakpoints do not.
+    BreakpointMismatch,
+    /// The target restricted mean survival time is not within `(0, tau)` for a finite `tau`.
+    InvalidRmstTarget,
+    /// The truncation point is not positive and finite.
+    InvalidTruncation,
+    /// The time scale factor is not positive and finite.
+    InvalidScaleFactor,
+    /// A survival milestone is out of order or outside `(0, 1)`.
+    InvalidMilestone {
+        /// Index of the offending milestone.
+        index: usize,
+    },
+    /// A truncated distribution was given where a piecewise constant hazard is required.
+    TruncatedHazard,
+    /// Arm counts are negative, not finite, or sum to zero.
+    InvalidArmCounts,
+    /// A horizon is `NaN`, negative, or smaller than the one before it.
+    InvalidHorizon {
+        /// Index of the offending horizon.
+        index: usize,
+    },
+    /// Observation times and event indicators have mismatched lengths.
+    ObservationLengthMismatch {
+        /// Number of observation times supplied.
+        times: usize,
+        /// Number of event indicators supplied.
+        events: usize,
+    },
+}
+
+impl fmt::Display for PiecewiseExponentialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PiecewiseExponentialError::EmptyIntervals => {
+                f.write_str("durations must contain at least one interval")
+            }
+            PiecewiseExponentialError::LengthMismatch { durations, rates } => write!(
+                f,
+                "durations and rates must have the same length ({} vs {})",
+                durations, rates
+            ),
             PiecewiseExponentialError::NonFiniteDuration { index } => {
                 write!(f, "duration at index {} must be finite", index)
             }
@@ -278,10 +1783,45 @@ impl fmt::Display for PiecewiseExponentialError {
             PiecewiseExponentialError::NonPositiveRate { index } => {
                 write!(f, "rate at index {} must be strictly positive", index)
             }
+            PiecewiseExponentialError::BreakpointMismatch => {
+                f.write_str("distributions must share identical breakpoints")
+            }
+            PiecewiseExponentialError::InvalidRmstTarget => {
+                f.write_str("target RMST must lie strictly between 0 and a finite tau")
+            }
+            PiecewiseExponentialError::InvalidTruncation => {
+                f.write_str("truncation point must be positive and finite")
+            }
+            PiecewiseExponentialError::InvalidScaleFactor => {
+                f.write_str("time scale factor must be positive and finite")
+            }
+            PiecewiseExponentialError::InvalidMilestone { index } => write!(
+                f,
+                "milestone at index {} must have an increasing finite time and a decreasing \
+                 survival within (0, 1)",
+                index
+            ),
+            PiecewiseExponentialError::TruncatedHazard => {
+                f.write_str("truncated distributions have no piecewise constant hazard")
+            }
+            PiecewiseExponentialError::InvalidArmCounts => {
+                f.write_str("arm counts must be non-negative and finite with a positive total")
+            }
+            PiecewiseExponentialError::InvalidHorizon { index } => write!(
+                f,
+                "horizon at index {} must be non-negative and no smaller than the one before it",
+                index
+            ),
+            PiecewiseExponentialError::ObservationLengthMismatch { times, events } => write!(
+                f,
+                "times and events must have the same length ({} vs {})",
+                times, events
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for PiecewiseExponentialError {}
 
 /// Errors that may occur while transforming explicit uniforms into samples.
@@ -292,6 +1832,42 @@ pub enum PiecewiseExponentialSampleError {
         /// The provided uniform variate.
         value: f64,
     },
+    /// The requested order statistic index did not satisfy `1 <= k <= m`.
+    OrderStatisticOutOfRange {
+        /// The requested order statistic index.
+        k: usize,
+        /// The number of draws the order statistic is taken over.
+        m: usize,
+    },
+    /// The conditioning window `(lower, upper]` is empty or has zero probability mass.
+    EmptyWindow {
+        /// Lower bound of the window.
+        lower: f64,
+        /// Upper bound of the window.
+        upper: f64,
+    },
+    /// The Halton sequence base is smaller than two.
+    InvalidHaltonBase {
+        /// The requested base.
+        base: usize,
+    },
+    /// The provided probability did not fall inside the interval `[0, 1)`.
+    ProbabilityOutOfRange {
+        /// The provided probability.
+        value: f64,
+    },
+    /// The requested quantile lies beyond the finite final boundary.
+    BeyondFinalBoundary {
+        /// The requested probability.
+        probability: f64,
+        /// End of the final interval.
+        boundary: f64,
+    },
+    /// The frailty variance is negative or not finite.
+    InvalidFrailtyVariance {
+        /// The provided variance.
+        variance: f64,
+    },
 }
 
 impl fmt::Display for PiecewiseExponentialSampleError {
@@ -302,8 +1878,40 @@ impl fmt::Display for PiecewiseExponentialSampleError {
                 "uniform variate {} must lie within the interval (0, 1]",
                 value
             ),
+            PiecewiseExponentialSampleError::OrderStatisticOutOfRange { k, m } => {
+                write!(f, "order statistic index {} must lie within 1..={}", k, m)
+            }
+            PiecewiseExponentialSampleError::EmptyWindow { lower, upper } => write!(
+                f,
+                "window ({}, {}] must satisfy 0 <= lower < upper to carry probability mass",
+                lower, upper
+            ),
+            PiecewiseExponentialSampleError::InvalidHaltonBase { base } => {
+                write!(f, "Halton sequence base {} must be at least 2", base)
+            }
+            PiecewiseExponentialSampleError::ProbabilityOutOfRange { value } => {
+                write!(
+                    f,
+                    "probability {} must lie within the interval [0, 1)",
+                    value
+                )
+            }
+            PiecewiseExponentialSampleError::BeyondFinalBoundary {
+                probability,
+                boundary,
+            } => write!(
+                f,
+                "quantile for probability {} lies beyond the final boundary {}",
+                probability, boundary
+            ),
+            PiecewiseExponentialSampleError::InvalidFrailtyVariance { variance } => write!(
+                f,
+                "frailty variance {} must be non-negative and finite",
+                variance
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for PiecewiseExponentialSampleError {}
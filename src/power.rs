@@ -0,0 +1,138 @@
+//! Summaries of simulated power and analytic event counts for the log-rank test.
+
+use alloc::vec::Vec;
+use rand::SeedableRng;
+use rand::distr::Distribution;
+use rand::rngs::StdRng;
+use rand_distr::Binomial;
+
+use crate::{math, normal};
+
+/// Compute a bootstrap percentile confidence interval for power from rejection indicators.
+///
+/// Each of the `bootstrap_reps` resamples draws `rejections.len()` indicators with replacement
+/// using a generator seeded with `seed`, and the interval spans the `(1 - level) / 2` and
+/// `(1 + level) / 2` quantiles of the resampled power estimates, interpolated linearly.
+///
+/// # Panics
+///
+/// Panics unless `0 < level < 1`, `rejections` is non-empty, and `bootstrap_reps > 0`.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::power_confidence_interval;
+///
+/// let rejections: Vec<bool> = (0..200).map(|i| i % 5 != 0).collect();
+/// let (lower, upper) = power_confidence_interval(&rejections, 0.95, 2000, 1);
+/// assert!(lower < 0.8 && 0.8 < upper);
+/// ```
+pub fn power_confidence_interval(
+    rejections: &[bool],
+    level: f64,
+    bootstrap_reps: usize,
+    seed: u64,
+) -> (f64, f64) {
+    assert!(
+        level > 0.0 && level < 1.0,
+        "confidence level must lie strictly between 0 and 1"
+    );
+    assert!(!rejections.is_empty(), "rejections must not be empty");
+    assert!(bootstrap_reps > 0, "bootstrap_reps must be positive");
+
+    let n = rejections.len();
+    let observed = rejections.iter().filter(|&&rejected| rejected).count() as f64 / n as f64;
+    // Resampling indicators with replacement only matters through the number of rejections
+    // drawn, which is binomial, so each replicate costs a single draw.
+    let resampled_count =
+        Binomial::new(n as u64, observed).expect("observed power is a probability");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut estimates: Vec<f64> = (0..bootstrap_reps)
+        .map(|_| resampled_count.sample(&mut rng) as f64 / n as f64)
+        .collect();
+    estimates.sort_by(f64::total_cmp);
+
+    let tail = 0.5 * (1.0 - level);
+    (quantile(&estimates, tail), quantile(&estimates, 1.0 - tail))
+}
+
+/// Compute the number of events needed by the log-rank test from the Schoenfeld formula.
+///
+/// The count is `(z_{1 - alpha} + z_{power})^2 / (r (1 - r) ln(hr)^2)` for a one-sided level
+/// `alpha` and a fraction `allocation = r` of patients on the experimental arm.
+///
+/// # Panics
+///
+/// Panics unless `hr` is positive, finite, and different from `1`, and `alpha`, `power`, and
+/// `allocation` each lie strictly between 0 and 1.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::events_for_power;
+///
+/// let events = events_for_power(0.7, 0.025, 0.9, 0.5);
+/// assert!((events - 330.4).abs() < 0.1);
+/// ```
+pub fn events_for_power(hr: f64, alpha: f64, power: f64, allocation: f64) -> f64 {
+    assert!(
+        hr > 0.0 && hr.is_finite() && hr != 1.0,
+        "hazard ratio must be positive, finite, and different from 1"
+    );
+    let drift = schoenfeld_drift(alpha, power, allocation);
+    let log_hr = math::ln(hr);
+    drift * drift / (allocation * (1.0 - allocation) * log_hr * log_hr)
+}
+
+/// Compute the hazard ratio below 1 that the log-rank test detects from `events` events.
+///
+/// This inverts [`events_for_power`]: `hr = exp(-(z_{1 - alpha} + z_{power}) / sqrt(r (1 - r)
+/// events))` for a one-sided level `alpha` and experimental allocation fraction `r`.
+///
+/// # Panics
+///
+/// Panics unless `events` is positive and finite, and `alpha`, `power`, and `allocation` each
+/// lie strictly between 0 and 1.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::detectable_hr;
+///
+/// let hr = detectable_hr(330.4, 0.025, 0.9, 0.5);
+/// assert!((hr - 0.7).abs() < 1e-3);
+/// ```
+pub fn detectable_hr(events: f64, alpha: f64, power: f64, allocation: f64) -> f64 {
+    assert!(
+        events > 0.0 && events.is_finite(),
+        "events must be positive and finite"
+    );
+    let drift = schoenfeld_drift(alpha, power, allocation);
+    math::exp(-drift / math::sqrt(allocation * (1.0 - allocation) * events))
+}
+
+/// Validate the design inputs and return `z_{1 - alpha} + z_{power}`.
+fn schoenfeld_drift(alpha: f64, power: f64, allocation: f64) -> f64 {
+    assert!(
+        alpha > 0.0 && alpha < 1.0,
+        "alpha must lie strictly between 0 and 1"
+    );
+    assert!(
+        power > 0.0 && power < 1.0,
+        "power must lie strictly between 0 and 1"
+    );
+    assert!(
+        allocation > 0.0 && allocation < 1.0,
+        "allocation must lie strictly between 0 and 1"
+    );
+    normal::quantile(1.0 - alpha) + normal::quantile(power)
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], probability: f64) -> f64 {
+    let position = probability * (sorted.len() - 1) as f64;
+    let lower = position as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = position - lower as f64;
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
@@ -0,0 +1,127 @@
+//! Analytic summaries for stratified designs.
+
+use alloc::vec::Vec;
+
+use crate::PiecewiseExponential;
+
+/// Number of Simpson panels used on each segment of the breakpoint grid.
+const PANELS: usize = 32;
+
+/// Compute the design effect of stratifying the log-rank test over `[0, tau]`.
+///
+/// Each stratum is `(weight, control, experimental)`, where `weight` is its relative size and
+/// patients are allocated 1:1 within strata and followed until `tau` without other censoring.
+/// The result is the expected stratified log-rank variance divided by the unstratified one,
+/// where each variance integrates the hypergeometric factor `n_ctrl n_exp / n^2` of its risk
+/// sets against the expected event rate. Event rates are taken from the densities, so
+/// [`truncate`](PiecewiseExponential::truncate)d arms, whose hazards are not constant between
+/// breakpoints, are integrated on a grid that also splits at their truncation points.
+///
+/// # Panics
+///
+/// Panics when `strata` is empty, a weight is not positive and finite, or `tau` is not positive
+/// and finite.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::{PiecewiseExponential, stratification_design_effect};
+///
+/// let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+/// let experimental = PiecewiseExponential::new(&[1.0], &[0.07]).unwrap();
+/// let strata = [(1.0, &control, &experimental), (2.0, &control, &experimental)];
+/// let effect = stratification_design_effect(&strata, 24.0);
+/// assert!((effect - 1.0).abs() < 1e-12);
+/// ```
+pub fn stratification_design_effect(
+    strata: &[(f64, &PiecewiseExponential, &PiecewiseExponential)],
+    tau: f64,
+) -> f64 {
+    assert!(!strata.is_empty(), "at least one stratum is required");
+    assert!(
+        strata
+            .iter()
+            .all(|&(weight, _, _)| weight.is_finite() && weight > 0.0),
+        "stratum weights must be positive and finite"
+    );
+    assert!(
+        tau.is_finite() && tau > 0.0,
+        "tau must be positive and finite"
+    );
+
+    let mut grid: Vec<f64> = strata
+        .iter()
+        .flat_map(|&(_, control, experimental)| {
+            control
+                .intervals()
+                .chain(experimental.intervals())
+                .map(|interval| interval.start)
+                .chain([control.support_end(), experimental.support_end()])
+        })
+        .filter(|&start| start < tau)
+        .collect();
+    grid.sort_by(f64::total_cmp);
+    grid.dedup();
+
+    let mut stratified = 0.0;
+    let mut unstratified = 0.0;
+    for (idx, &start) in grid.iter().enumerate() {
+        let end = grid.get(idx + 1).copied().unwrap_or(tau);
+        let integrands = |t: f64| {
+            let (mut at_risk_ctrl, mut at_risk_exp) = (0.0, 0.0);
+            let (mut events_ctrl, mut events_exp) = (0.0, 0.0);
+            let mut stratified_rate = 0.0;
+            for &(weight, control, experimental) in strata {
+                let (ctrl, exp) = (control.survival(t), experimental.survival(t));
+                let (ctrl_events, exp_events) = (control.density(t), experimental.density(t));
+                stratified_rate += 0.5
+                    * weight
+                    * balance(ctrl, exp, ctrl_events, exp_events)
+                    * (ctrl_events + exp_events);
+                at_risk_ctrl += weight * ctrl;
+                at_risk_exp += weight * exp;
+                events_ctrl += weight * ctrl_events;
+                events_exp += weight * exp_events;
+            }
+            (
+                stratified_rate,
+                0.5 * balance(at_risk_ctrl, at_risk_exp, events_ctrl, events_exp)
+                    * (events_ctrl + events_exp),
+            )
+        };
+
+        let step = (end - start) / (2 * PANELS) as f64;
+        for node in 0..=2 * PANELS {
+            let simpson = if node == 0 || node == 2 * PANELS {
+                1.0
+            } else if node % 2 == 1 {
+                4.0
+            } else {
+                2.0
+            };
+            let (stratified_value, unstratified_value) = integrands(start + node as f64 * step);
+            stratified += simpson * step / 3.0 * stratified_value;
+            unstratified += simpson * step / 3.0 * unstratified_value;
+        }
+    }
+    stratified / unstratified
+}
+
+/// Hypergeometric factor `n_ctrl n_exp / n^2` of a risk set with `ctrl` and `exp` at risk.
+///
+/// When both arms reach their truncation points together the risk set empties, and the factor
+/// takes its limit from the left, where the numbers at risk shrink in proportion to the event
+/// densities `ctrl_events` and `exp_events`.
+fn balance(ctrl: f64, exp: f64, ctrl_events: f64, exp_events: f64) -> f64 {
+    let (ctrl, exp) = if ctrl + exp > 0.0 {
+        (ctrl, exp)
+    } else {
+        (ctrl_events, exp_events)
+    };
+    let total = ctrl + exp;
+    if total > 0.0 {
+        ctrl * exp / (total * total)
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,27 @@
+//! Weight functions for weighted log-rank tests.
+
+use alloc::vec::Vec;
+
+use crate::math;
+
+/// Compute Fleming-Harrington `FH(rho, gamma)` weights from a pooled survival vector.
+///
+/// Each element of `pooled` is the left-continuous pooled survival `S(t-)` at a risk set, and
+/// the returned weight is `S(t-)^rho * (1 - S(t-))^gamma`. Computing the pooled survival once
+/// lets several weightings, such as the components of a MaxCombo test, share it.
+///
+/// # Examples
+///
+/// ```
+/// use simtrial::fh_weights;
+///
+/// let pooled = [1.0, 0.75, 0.5];
+/// assert_eq!(fh_weights(&pooled, 0.0, 0.0), [1.0, 1.0, 1.0]);
+/// assert_eq!(fh_weights(&pooled, 0.0, 1.0), [0.0, 0.25, 0.5]);
+/// ```
+pub fn fh_weights(pooled: &[f64], rho: f64, gamma: f64) -> Vec<f64> {
+    pooled
+        .iter()
+        .map(|&survival| math::powf(survival, rho) * math::powf(1.0 - survival, gamma))
+        .collect()
+}
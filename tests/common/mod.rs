@@ -68,3 +68,61 @@ pub fn assert_close_slice(actual: &[f64], expected: &[f64]) {
         );
     }
 }
+
+/// Reproduce `set.seed(seed); runif(n)` from R's default Mersenne-Twister generator.
+///
+/// R scrambles the seed with 50 rounds of the LCG `69069 * s + 1`, fills the 624-word state with
+/// further LCG steps (the first of 625 words holds the position and is reset to 624), and maps
+/// each tempered 32-bit output to `(0, 1)` via `fixup(y * 2^-32)`.
+pub fn r_compatible_uniforms(seed: u32, n: usize) -> Vec<f64> {
+    const N: usize = 624;
+    const M: usize = 397;
+    const MATRIX_A: u32 = 0x9908_b0df;
+    const UPPER_MASK: u32 = 0x8000_0000;
+    const LOWER_MASK: u32 = 0x7fff_ffff;
+    const I2_32M1: f64 = 2.328306437080797e-10;
+
+    let lcg = |s: u32| s.wrapping_mul(69069).wrapping_add(1);
+
+    let mut scrambled = seed;
+    for _ in 0..50 {
+        scrambled = lcg(scrambled);
+    }
+    // Word 0 of R's seed vector stores `mti` and is overwritten, so it is drawn but discarded.
+    scrambled = lcg(scrambled);
+    let mut mt = [0_u32; N];
+    for word in mt.iter_mut() {
+        scrambled = lcg(scrambled);
+        *word = scrambled;
+    }
+    let mut mti = N;
+
+    let mag01 = |y: u32| if y & 1 == 0 { 0 } else { MATRIX_A };
+    let mut uniforms = Vec::with_capacity(n);
+    for _ in 0..n {
+        if mti >= N {
+            for kk in 0..N {
+                let y = (mt[kk] & UPPER_MASK) | (mt[(kk + 1) % N] & LOWER_MASK);
+                mt[kk] = mt[(kk + M) % N] ^ (y >> 1) ^ mag01(y);
+            }
+            mti = 0;
+        }
+        let mut y = mt[mti];
+        mti += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c_5680;
+        y ^= (y << 15) & 0xefc6_0000;
+        y ^= y >> 18;
+
+        let value = f64::from(y) * 2.3283064365386963e-10;
+        let fixed = if value <= 0.0 {
+            0.5 * I2_32M1
+        } else if 1.0 - value <= 0.0 {
+            1.0 - 0.5 * I2_32M1
+        } else {
+            value
+        };
+        uniforms.push(fixed);
+    }
+    uniforms
+}
@@ -0,0 +1,315 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use simtrial::{
+    PiecewiseExponential, PiecewiseExponentialError, hazard_ratio_grid, number_needed_to_treat,
+    pooled_by_counts, rmst_difference_curve, survival_crossing_times, survival_difference_integral,
+    win_probability,
+};
+
+mod common;
+
+use common::assert_close_slice;
+
+#[test]
+fn number_needed_to_treat_inverts_survival_difference() {
+    // Survival at 12 months is 0.5 for control and 0.75 for experimental.
+    let control = PiecewiseExponential::new(&[1.0], &[2.0_f64.ln() / 12.0]).unwrap();
+    let experimental = PiecewiseExponential::new(&[1.0], &[(4.0_f64 / 3.0).ln() / 12.0]).unwrap();
+
+    let nnt = number_needed_to_treat(&control, &experimental, 12.0);
+    assert_close_slice(&[nnt], &[4.0]);
+
+    let nnh = number_needed_to_treat(&experimental, &control, 12.0);
+    assert_close_slice(&[nnh], &[-4.0]);
+
+    assert_eq!(
+        number_needed_to_treat(&control, &control, 12.0),
+        f64::INFINITY
+    );
+}
+
+#[test]
+fn hazard_ratio_grid_captures_delayed_effect() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.07]).unwrap();
+    let times = [0.0, 1.5, 2.999, 3.0, 6.0, 24.0];
+
+    let ratios = hazard_ratio_grid(&control, &experimental, &times);
+    assert_close_slice(&ratios, &[1.0, 1.0, 1.0, 0.7, 0.7, 0.7]);
+
+    assert_eq!(
+        hazard_ratio_grid(&control, &experimental, &[-1.0]),
+        [f64::INFINITY]
+    );
+}
+
+#[test]
+fn win_probability_is_half_for_identical_arms_and_grows_with_benefit() {
+    let control = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.1, 0.3, 0.05]).unwrap();
+    assert_close_slice(&[win_probability(&control, &control)], &[0.5]);
+
+    let mut previous = 0.0;
+    for experimental_rate in [0.2, 0.1, 0.07, 0.05, 0.02] {
+        let experimental =
+            PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, experimental_rate]).unwrap();
+        let probability = win_probability(&control, &experimental);
+        assert!(probability > previous);
+        previous = probability;
+    }
+}
+
+#[test]
+fn win_probability_matches_simulation_with_a_truncated_arm() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let truncated = PiecewiseExponential::new(&[1.0], &[0.3])
+        .unwrap()
+        .truncate(4.0)
+        .unwrap();
+
+    let mut rng = StdRng::seed_from_u64(426);
+    let n = 200_000;
+    let wins = (0..n)
+        .filter(|_| truncated.sample(&mut rng) > control.sample(&mut rng))
+        .count() as f64
+        / n as f64;
+
+    let probability = win_probability(&control, &truncated);
+    assert!((wins - probability).abs() < 0.005);
+    assert_close_slice(
+        &[probability + win_probability(&truncated, &control)],
+        &[1.0],
+    );
+}
+
+#[test]
+fn survival_difference_integral_is_rmst_difference() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.06]).unwrap();
+
+    for tau in [1.0, 3.0, 12.0, 36.0] {
+        let expected = experimental.rmst(tau) - control.rmst(tau);
+        assert_close_slice(
+            &[survival_difference_integral(&control, &experimental, tau)],
+            &[expected],
+        );
+    }
+    // Identical hazards before the delayed effect leave no difference.
+    assert_close_slice(
+        &[survival_difference_integral(&control, &experimental, 3.0)],
+        &[0.0],
+    );
+}
+
+#[test]
+fn pooled_by_counts_weights_hazards_by_allocation() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.06]).unwrap();
+
+    let equal = pooled_by_counts(&control, &experimental, 150.0, 150.0).unwrap();
+    let unit = pooled_by_counts(&control, &experimental, 1.0, 1.0).unwrap();
+    let times = [0.0, 2.0, 3.0, 10.0];
+    let averaged: Vec<f64> = times
+        .iter()
+        .map(|&t| 0.5 * (control.hazard_at(t) + experimental.hazard_at(t)))
+        .collect();
+    let equal_rates: Vec<f64> = times.iter().map(|&t| equal.hazard_at(t)).collect();
+    let unit_rates: Vec<f64> = times.iter().map(|&t| unit.hazard_at(t)).collect();
+    assert_close_slice(&equal_rates, &averaged);
+    assert_close_slice(&equal_rates, &unit_rates);
+
+    let unequal = pooled_by_counts(&control, &experimental, 100.0, 300.0).unwrap();
+    assert_close_slice(&[unequal.hazard_at(5.0)], &[0.25 * 0.1 + 0.75 * 0.06]);
+    let control_only = pooled_by_counts(&control, &experimental, 10.0, 0.0).unwrap();
+    assert_close_slice(&[control_only.hazard_at(5.0)], &[0.1]);
+}
+
+#[test]
+fn pooled_by_counts_keeps_a_shared_finite_boundary() {
+    let control = PiecewiseExponential::new(&[1.0, 2.0], &[0.1, 0.2]).unwrap();
+    let experimental = PiecewiseExponential::new(&[2.0, 1.0], &[0.05, 0.3]).unwrap();
+
+    let pooled = pooled_by_counts(&control, &experimental, 1.0, 1.0).unwrap();
+    assert!(!pooled.has_open_tail());
+    let ends: Vec<f64> = pooled.intervals().map(|interval| interval.end).collect();
+    assert_eq!(ends, [1.0, 2.0, 3.0]);
+    assert_close_slice(&[pooled.hazard_at(2.5)], &[0.25]);
+
+    // An open tail in either arm keeps the pooled tail open.
+    let open = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.1, 0.2]).unwrap();
+    let pooled = pooled_by_counts(&open, &experimental, 1.0, 1.0).unwrap();
+    assert!(pooled.has_open_tail());
+}
+
+#[test]
+fn pooled_by_counts_rejects_invalid_counts() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    for (n_ctrl, n_exp) in [
+        (0.0, 0.0),
+        (-1.0, 2.0),
+        (1.0, f64::NAN),
+        (f64::INFINITY, 1.0),
+    ] {
+        assert_eq!(
+            pooled_by_counts(&control, &control, n_ctrl, n_exp).unwrap_err(),
+            PiecewiseExponentialError::InvalidArmCounts
+        );
+    }
+}
+
+#[test]
+fn pooled_by_counts_rejects_truncated_arms() {
+    let truncated = PiecewiseExponential::new(&[1.0], &[0.3])
+        .unwrap()
+        .truncate(4.0)
+        .unwrap();
+    let other = PiecewiseExponential::new(&[2.0, 6.0, f64::INFINITY], &[0.2, 0.1, 0.05]).unwrap();
+
+    for (control, experimental) in [(&truncated, &other), (&other, &truncated)] {
+        assert_eq!(
+            pooled_by_counts(control, experimental, 1.0, 1.0).unwrap_err(),
+            PiecewiseExponentialError::TruncatedHazard
+        );
+    }
+}
+
+#[test]
+fn survival_crossing_times_find_the_single_known_crossing() {
+    // Early harm then benefit: H_exp - H_ctrl = 0.3 - 0.08 * (t - 3) after month 3, zero at 6.75.
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.2, 0.02]).unwrap();
+
+    let crossings = survival_crossing_times(&control, &experimental, 24.0).unwrap();
+    assert_close_slice(&crossings, &[6.75]);
+    assert_close_slice(
+        &[control.survival(crossings[0])],
+        &[experimental.survival(crossings[0])],
+    );
+
+    assert!(
+        survival_crossing_times(&control, &experimental, 6.0)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        survival_crossing_times(&control, &experimental, f64::INFINITY)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        survival_crossing_times(&experimental, &control, 24.0)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(
+        survival_crossing_times(&control, &control, 24.0)
+            .unwrap()
+            .is_empty()
+    );
+
+    // Proportional hazards never cross.
+    let better = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.05, 0.05]).unwrap();
+    assert!(
+        survival_crossing_times(&control, &better, 100.0)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn survival_crossing_times_reject_truncated_arms() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let truncated = PiecewiseExponential::new(&[1.0], &[0.05])
+        .unwrap()
+        .truncate(10.0)
+        .unwrap();
+    for (first, second) in [(&control, &truncated), (&truncated, &control)] {
+        assert!(survival_crossing_times(first, second, 24.0).is_err());
+        assert_eq!(
+            survival_crossing_times(first, second, 24.0).unwrap_err(),
+            PiecewiseExponentialError::TruncatedHazard
+        );
+    }
+}
+
+#[test]
+fn survival_crossing_times_require_a_strict_sign_change() {
+    // H_exp - H_ctrl falls to -0.1 at month 2, climbs back to touch zero at month 4, then falls.
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    let touching =
+        PiecewiseExponential::new(&[2.0, 2.0, f64::INFINITY], &[0.05, 0.15, 0.05]).unwrap();
+    assert_close_slice(
+        &[touching.cumulative_hazard_at(4.0)],
+        &[control.cumulative_hazard_at(4.0)],
+    );
+    for tau in [3.0, 4.0, 24.0, f64::INFINITY] {
+        assert!(
+            survival_crossing_times(&control, &touching, tau)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    // The same touch followed by a rise is a crossing at the breakpoint.
+    let crossing =
+        PiecewiseExponential::new(&[2.0, 2.0, f64::INFINITY], &[0.05, 0.15, 0.2]).unwrap();
+    for tau in [4.0, 24.0] {
+        let crossings = survival_crossing_times(&control, &crossing, tau).unwrap();
+        assert_close_slice(&crossings, &[4.0]);
+    }
+
+    // Curves that coincide over a stretch cross at its start only when the order flips.
+    let coinciding =
+        PiecewiseExponential::new(&[2.0, 2.0, 2.0, f64::INFINITY], &[0.05, 0.15, 0.1, 0.2])
+            .unwrap();
+    let crossings = survival_crossing_times(&control, &coinciding, 24.0).unwrap();
+    assert_close_slice(&crossings, &[4.0]);
+    let rejoining =
+        PiecewiseExponential::new(&[2.0, 2.0, 2.0, f64::INFINITY], &[0.05, 0.15, 0.1, 0.05])
+            .unwrap();
+    assert!(
+        survival_crossing_times(&control, &rejoining, 24.0)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn rmst_difference_curve_matches_per_horizon_rmst() {
+    let control = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.1, 0.2, 0.15]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, 1.0], &[0.08, 0.05])
+        .unwrap()
+        .truncate(20.0)
+        .unwrap();
+    let taus = [0.0, 1.5, 2.0, 3.0, 3.0, 7.5, 12.0, 25.0, f64::INFINITY];
+
+    let curve = rmst_difference_curve(&control, &experimental, &taus).unwrap();
+    let expected: Vec<f64> = taus
+        .iter()
+        .map(|&tau| experimental.rmst(tau) - control.rmst(tau))
+        .collect();
+    assert_close_slice(&curve, &expected);
+    assert!(
+        rmst_difference_curve(&control, &experimental, &[])
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn rmst_difference_curve_rejects_invalid_horizons() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+    for (taus, index) in [
+        (&[12.0, 6.0][..], 1),
+        (&[6.0, f64::NAN, 12.0][..], 1),
+        (&[f64::NAN][..], 0),
+        (&[-1.0, 6.0][..], 0),
+        (&[f64::INFINITY, 12.0][..], 1),
+    ] {
+        assert_eq!(
+            rmst_difference_curve(&control, &control, taus).unwrap_err(),
+            PiecewiseExponentialError::InvalidHorizon { index }
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use simtrial::{PiecewiseExponential, net_event_probability};
+
+mod common;
+
+use common::assert_close_slice;
+
+#[test]
+fn net_event_probability_reduces_to_cdf_without_dropout() {
+    let failure = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.05]).unwrap();
+    // Rates must be positive, so the smallest positive rate stands in for no dropout.
+    let no_dropout = PiecewiseExponential::new(&[1.0], &[f64::MIN_POSITIVE]).unwrap();
+
+    for tau in [0.5, 3.0, 12.0] {
+        assert_close_slice(
+            &[net_event_probability(&failure, &no_dropout, tau)],
+            &[failure.cdf(tau)],
+        );
+    }
+    assert_eq!(net_event_probability(&failure, &no_dropout, 0.0), 0.0);
+}
+
+#[test]
+fn net_event_probability_matches_simulated_competing_risks() {
+    let failure = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.05]).unwrap();
+    let dropout = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.01, 0.03]).unwrap();
+    let tau = 18.0;
+
+    let mut rng = StdRng::seed_from_u64(2424);
+    let n = 200_000;
+    let observed = (0..n)
+        .filter(|_| {
+            let event = failure.sample(&mut rng);
+            let censor = dropout.sample(&mut rng);
+            event < censor && event <= tau
+        })
+        .count() as f64
+        / n as f64;
+
+    let expected = net_event_probability(&failure, &dropout, tau);
+    assert!(expected < failure.cdf(tau));
+    assert!((observed - expected).abs() < 0.005);
+}
+
+#[test]
+fn net_event_probability_handles_truncated_arms() {
+    let truncated = PiecewiseExponential::new(&[1.0], &[0.3])
+        .unwrap()
+        .truncate(4.0)
+        .unwrap();
+    let other = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.1, 0.2]).unwrap();
+
+    let mut rng = StdRng::seed_from_u64(2425);
+    let n = 200_000;
+    let draws: Vec<(f64, f64)> = (0..n)
+        .map(|_| (truncated.sample(&mut rng), other.sample(&mut rng)))
+        .collect();
+    for tau in [2.0, 3.0, f64::INFINITY] {
+        let observed = draws
+            .iter()
+            .filter(|&&(first, second)| first < second && first <= tau)
+            .count() as f64
+            / n as f64;
+        let expected = net_event_probability(&truncated, &other, tau);
+        assert!((observed - expected).abs() < 0.005, "tau {tau}");
+
+        let observed = draws
+            .iter()
+            .filter(|&&(first, second)| second < first && second <= tau)
+            .count() as f64
+            / n as f64;
+        let expected = net_event_probability(&other, &truncated, tau);
+        assert!((observed - expected).abs() < 0.005, "tau {tau}");
+    }
+
+    // Failure and dropout together exhaust the probability when both are truncated.
+    let both = net_event_probability(&truncated, &truncated, f64::INFINITY);
+    assert_close_slice(&[both], &[0.5]);
+}
@@ -0,0 +1,24 @@
+//! Checks the sampler self-diagnostics behind the `diagnostics` feature.
+//!
+//! Run with `cargo test --features diagnostics`.
+#![cfg(feature = "diagnostics")]
+
+use simtrial::PiecewiseExponential;
+
+#[test]
+fn chi_square_uniformity_stays_below_critical_value() {
+    // 0.999 quantile of the chi-square distribution with 19 degrees of freedom.
+    const CRITICAL_VALUE: f64 = 43.82;
+
+    let dist = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+    for seed in [1, 2, 3] {
+        let statistic = dist.chi_square_uniformity(100_000, 20, seed);
+        assert!(
+            statistic < CRITICAL_VALUE,
+            "seed {seed}: statistic {statistic} exceeds {CRITICAL_VALUE}"
+        );
+    }
+
+    assert!(dist.chi_square_uniformity(0, 20, 1).is_nan());
+    assert!(dist.chi_square_uniformity(100, 0, 1).is_nan());
+}
@@ -0,0 +1,54 @@
+use simtrial::{obrien_fleming_boundaries, pocock_boundaries};
+
+#[test]
+fn single_final_look_matches_fixed_design_critical_value() {
+    // Upper quantiles of the standard normal distribution.
+    for (alpha, critical) in [
+        (0.025, 1.959_963_984_540_054),
+        (0.05, 1.644_853_626_951_472_2),
+        (0.001, 3.090_232_306_167_813_5),
+    ] {
+        let boundaries = obrien_fleming_boundaries(&[1.0], alpha);
+        assert_eq!(boundaries.len(), 1);
+        assert!((boundaries[0] - critical).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn obrien_fleming_boundaries_match_reference_design() {
+    // Lan-DeMets O'Brien-Fleming bounds for three equally spaced looks at one-sided 0.025,
+    // as reported by gsDesign.
+    let boundaries = obrien_fleming_boundaries(&[1.0 / 3.0, 2.0 / 3.0, 1.0], 0.025);
+    let expected = [3.7103, 2.5114, 1.9930];
+    for (actual, expected) in boundaries.iter().zip(expected) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} vs {expected}");
+    }
+    assert!(boundaries.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn pocock_boundaries_are_nearly_constant_unlike_obrien_fleming() {
+    let fractions = [0.2, 0.4, 0.6, 0.8, 1.0];
+    let range = |values: &[f64]| {
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        max - min
+    };
+
+    let pocock = pocock_boundaries(&fractions, 0.025);
+    let obrien_fleming = obrien_fleming_boundaries(&fractions, 0.025);
+    assert!(range(&pocock) < 0.1);
+    assert!(range(&obrien_fleming) > 1.0);
+    // Spending early costs power at the final analysis.
+    assert!(pocock[4] > obrien_fleming[4]);
+    assert!(pocock[0] < obrien_fleming[0]);
+
+    let single = pocock_boundaries(&[1.0], 0.025);
+    assert!((single[0] - 1.959_963_984_540_054).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "information fractions must be strictly increasing within (0, 1]")]
+fn boundaries_reject_unordered_information_fractions() {
+    obrien_fleming_boundaries(&[0.5, 0.5, 1.0], 0.025);
+}
@@ -0,0 +1,53 @@
+use simtrial::{Adjustment, PValueError, adjust_pvalues};
+
+mod common;
+
+use common::assert_close_slice;
+
+#[test]
+fn bonferroni_and_holm_match_hand_computed_adjustments() {
+    let pvalues = [0.04, 0.001, 0.03, 0.2, 0.012];
+
+    // Bonferroni multiplies by m = 5 and caps at 1.
+    assert_close_slice(
+        &adjust_pvalues(&pvalues, Adjustment::Bonferroni).unwrap(),
+        &[0.2, 0.005, 0.15, 1.0, 0.06],
+    );
+
+    // Sorted: 0.001 * 5, 0.012 * 4, 0.03 * 3, 0.04 * 2, 0.2 * 1 = 0.005, 0.048, 0.09, 0.08, 0.2;
+    // the running maximum lifts 0.08 to 0.09.
+    assert_close_slice(
+        &adjust_pvalues(&pvalues, Adjustment::Holm).unwrap(),
+        &[0.09, 0.005, 0.09, 0.2, 0.048],
+    );
+
+    assert!(adjust_pvalues(&[], Adjustment::Holm).unwrap().is_empty());
+    assert_eq!(
+        adjust_pvalues(&[0.3, 0.6], Adjustment::Holm).unwrap(),
+        [0.6, 0.6]
+    );
+}
+
+#[test]
+fn adjust_pvalues_rejects_out_of_range_values() {
+    for method in [Adjustment::Bonferroni, Adjustment::Holm] {
+        assert_eq!(
+            adjust_pvalues(&[0.5, 1.2], method).unwrap_err(),
+            PValueError::OutOfRange {
+                index: 1,
+                value: 1.2
+            }
+        );
+        assert_eq!(
+            adjust_pvalues(&[-0.1], method).unwrap_err(),
+            PValueError::OutOfRange {
+                index: 0,
+                value: -0.1
+            }
+        );
+        assert!(matches!(
+            adjust_pvalues(&[0.2, 0.3, f64::NAN], method).unwrap_err(),
+            PValueError::OutOfRange { index: 2, value } if value.is_nan()
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+//! Checks the `libm` math path used when the `std` feature is disabled.
+//!
+//! Run with `cargo test --no-default-features --features libm`.
+#![cfg(not(feature = "std"))]
+
+use simtrial::PiecewiseExponential;
+
+mod common;
+
+use common::{assert_close_slice, load_columns};
+
+#[test]
+fn libm_inverse_cdf_matches_std_inverse_transform() {
+    let durations = [0.5, 0.5, 1.0];
+    let rates = [1.0, 3.0, 10.0];
+    let dist = PiecewiseExponential::new(&durations, &rates).unwrap();
+
+    let uniforms: Vec<f64> = (1..=200).map(|i| i as f64 / 200.0).collect();
+    let actual: Vec<f64> = uniforms
+        .iter()
+        .map(|&u| dist.inverse_cdf(u).unwrap())
+        .collect();
+    let expected: Vec<f64> = uniforms
+        .iter()
+        .map(|&u| {
+            let hazard = -u.ln();
+            if hazard < 0.5 {
+                hazard
+            } else if hazard < 2.0 {
+                0.5 + (hazard - 0.5) / 3.0
+            } else {
+                1.0 + (hazard - 2.0) / 10.0
+            }
+        })
+        .collect();
+
+    assert_close_slice(&actual, &expected);
+}
+
+#[test]
+fn libm_inverse_cdf_matches_r_reference() {
+    let columns = load_columns("pwexp_multi_seed_456_n30.txt");
+    let dist = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+
+    let actual: Vec<f64> = columns[0]
+        .iter()
+        .map(|&u| dist.inverse_cdf(u).unwrap())
+        .collect();
+
+    assert_close_slice(&actual, &columns[1]);
+}
@@ -2,7 +2,7 @@ use simtrial::PiecewiseExponential;
 
 mod common;
 
-use common::{assert_close_slice, load_columns};
+use common::{assert_close_slice, load_columns, r_compatible_uniforms};
 
 #[test]
 fn inverse_cdf_matches_r_reference_single_interval() {
@@ -36,3 +36,25 @@ fn inverse_cdf_matches_r_reference_multi_interval() {
 
     assert_close_slice(&actual, expected);
 }
+
+#[test]
+fn r_compatible_uniforms_match_recorded_set_seed_streams() {
+    let single = load_columns("pwexp_single_seed_123_n20.txt");
+    assert_close_slice(&r_compatible_uniforms(123, 20), &single[0]);
+
+    let multi = load_columns("pwexp_multi_seed_456_n30.txt");
+    assert_close_slice(&r_compatible_uniforms(456, 30), &multi[0]);
+}
+
+#[test]
+fn r_compatible_uniforms_reproduce_rpwexp_end_to_end() {
+    let expected = load_columns("pwexp_multi_seed_456_n30.txt");
+    let dist = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+
+    let actual: Vec<f64> = r_compatible_uniforms(456, 30)
+        .into_iter()
+        .map(|u| dist.inverse_cdf(u).unwrap())
+        .collect();
+
+    assert_close_slice(&actual, &expected[1]);
+}
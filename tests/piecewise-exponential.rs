@@ -1,7 +1,10 @@
 use rand::distr::Open01;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use simtrial::{PiecewiseExponential, PiecewiseExponentialError, PiecewiseExponentialSampleError};
+use simtrial::{
+    HazardShape, Interval, PiecewiseExponential, PiecewiseExponentialError,
+    PiecewiseExponentialSampleError, QuantileMode,
+};
 
 mod common;
 
@@ -168,3 +171,1044 @@ fn invalid_parameters_trigger_informative_errors() {
         PiecewiseExponentialError::NonPositiveFinalDuration
     ));
 }
+
+#[test]
+fn first_order_statistic_matches_minimum_of_naive_draws() {
+    let dist = PiecewiseExponential::new(&[0.1, f64::INFINITY], &[1.0, 3.0]).unwrap();
+    let m = 5;
+    let replicates = 20_000;
+
+    let mut rng_order = StdRng::seed_from_u64(31);
+    let mut order: Vec<f64> = (0..replicates)
+        .map(|_| dist.sample_order_statistic(m, 1, &mut rng_order).unwrap())
+        .collect();
+
+    let mut rng_naive = StdRng::seed_from_u64(32);
+    let mut naive: Vec<f64> = (0..replicates)
+        .map(|_| {
+            dist.sample_n(m, &mut rng_naive)
+                .into_iter()
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect();
+
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    assert!((mean(&order) - mean(&naive)).abs() < 0.003);
+
+    order.sort_by(f64::total_cmp);
+    naive.sort_by(f64::total_cmp);
+    for quantile in [0.25, 0.5, 0.75] {
+        let idx = (quantile * replicates as f64) as usize;
+        assert!((order[idx] - naive[idx]).abs() < 0.005);
+    }
+}
+
+#[test]
+fn order_statistic_rejects_invalid_index() {
+    let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    let mut rng = StdRng::seed_from_u64(5);
+
+    for (m, k) in [(5, 0), (5, 6), (0, 0)] {
+        assert_eq!(
+            dist.sample_order_statistic(m, k, &mut rng).unwrap_err(),
+            PiecewiseExponentialSampleError::OrderStatisticOutOfRange { k, m }
+        );
+    }
+    assert!(dist.sample_order_statistic(5, 5, &mut rng).is_ok());
+}
+
+#[test]
+fn inverse_cdf_is_monotone_across_interval_boundaries() {
+    let durations = [0.5, 0.5, 1.0];
+    let rates = [1.0, 3.0, 10.0];
+    let dist = PiecewiseExponential::new(&durations, &rates).unwrap();
+
+    // Survival at the breakpoints is exp(-0.5) and exp(-2.0); pack the grid densely around them.
+    let mut grid: Vec<f64> = (1..=1000).map(|i| i as f64 / 1000.0).collect();
+    for boundary in [(-0.5_f64).exp(), (-2.0_f64).exp()] {
+        for offset in -5..=5 {
+            grid.push(boundary + offset as f64 * 1e-12);
+        }
+    }
+
+    assert!(dist.inverse_cdf_is_monotone(&grid));
+    assert!(!dist.inverse_cdf_is_monotone(&[0.5, f64::NAN]));
+}
+
+#[test]
+fn window_samples_follow_doubly_truncated_distribution() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    let (t0, t_max) = (0.5, 1.5);
+    let mut rng = StdRng::seed_from_u64(2718);
+
+    let draws: Vec<f64> = (0..20_000)
+        .map(|_| dist.sample_window(t0, t_max, &mut rng).unwrap())
+        .collect();
+    assert!(draws.iter().all(|&t| t > t0 && t < t_max));
+
+    let survival = |t: f64| (-dist.cumulative_hazard_at(t)).exp();
+    for &t in &[0.75, 1.0, 1.25] {
+        let empirical = draws.iter().filter(|&&x| x <= t).count() as f64 / draws.len() as f64;
+        let analytic = (survival(t0) - survival(t)) / (survival(t0) - survival(t_max));
+        assert!((empirical - analytic).abs() < 0.015);
+    }
+}
+
+#[test]
+fn window_with_open_upper_bound_is_left_truncation() {
+    let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    let mut rng = StdRng::seed_from_u64(77);
+
+    let draws: Vec<f64> = (0..20_000)
+        .map(|_| dist.sample_window(3.0, f64::INFINITY, &mut rng).unwrap())
+        .collect();
+    let mean_excess = draws.iter().map(|t| t - 3.0).sum::<f64>() / draws.len() as f64;
+
+    assert!(draws.iter().all(|&t| t > 3.0 && t.is_finite()));
+    assert!((mean_excess - 0.5).abs() < 0.02);
+}
+
+#[test]
+fn window_without_probability_mass_is_rejected() {
+    let dist = PiecewiseExponential::new(&[1.0], &[2.0]).unwrap();
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for (lower, upper) in [(1.0, 1.0), (2.0, 1.0), (-0.5, 1.0), (f64::NAN, 1.0)] {
+        assert!(matches!(
+            dist.sample_window(lower, upper, &mut rng).unwrap_err(),
+            PiecewiseExponentialSampleError::EmptyWindow { .. }
+        ));
+    }
+}
+
+#[test]
+fn narrow_window_far_out_is_rejected_rather_than_returning_its_start() {
+    let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    let mut rng = StdRng::seed_from_u64(384);
+
+    // The hazard increment across the window is below the resolution of the cumulative hazard,
+    // so a draw either lands strictly inside the window or the window is reported as empty.
+    let (t0, t_max) = (1e6, 1e6 + 1e-10);
+    for _ in 0..100 {
+        match dist.sample_window(t0, t_max, &mut rng) {
+            Ok(draw) => assert!(draw > t0 && draw <= t_max),
+            Err(error) => assert!(matches!(
+                error,
+                PiecewiseExponentialSampleError::EmptyWindow { .. }
+            )),
+        }
+    }
+    let draw = dist.sample_window(1e6, 1e6 + 1.0, &mut rng).unwrap();
+    assert!(draw > 1e6 && draw <= 1e6 + 1.0);
+
+    let truncated = dist.truncate(4.0).unwrap();
+    assert!(matches!(
+        truncated.sample_window(4.0, 5.0, &mut rng).unwrap_err(),
+        PiecewiseExponentialSampleError::EmptyWindow { .. }
+    ));
+}
+
+#[test]
+fn hazard_ratio_by_interval_divides_rates() {
+    let control = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.2, 0.1, 0.05]).unwrap();
+    let experimental =
+        PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.2, 0.05, 0.04]).unwrap();
+
+    let ratios = control.hazard_ratio_by_interval(&experimental).unwrap();
+    assert_close_slice(&ratios, &[1.0, 0.5, 0.8]);
+}
+
+#[test]
+fn hazard_ratio_by_interval_requires_shared_breakpoints() {
+    let control = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.2, 0.1]).unwrap();
+    let shifted = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.2, 0.1]).unwrap();
+    let extra = PiecewiseExponential::new(&[2.0, 1.0, f64::INFINITY], &[0.2, 0.1, 0.1]).unwrap();
+
+    for other in [&shifted, &extra] {
+        assert_eq!(
+            control.hazard_ratio_by_interval(other).unwrap_err(),
+            PiecewiseExponentialError::BreakpointMismatch
+        );
+    }
+    assert_eq!(
+        control
+            .hazard_ratio_by_interval(&control.truncate(5.0).unwrap())
+            .unwrap_err(),
+        PiecewiseExponentialError::TruncatedHazard
+    );
+}
+
+#[test]
+fn survival_sparkline_has_requested_width_and_starts_full() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.2, 1.5]).unwrap();
+
+    let line = dist.survival_sparkline(4.0, 20);
+    let chars: Vec<char> = line.chars().collect();
+    assert_eq!(chars.len(), 20);
+    assert_eq!(chars[0], '█');
+    assert_eq!(chars[19], '▁');
+    assert!(chars.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    assert!(dist.survival_sparkline(4.0, 0).is_empty());
+    assert_eq!(dist.survival_sparkline(4.0, 1), "█");
+}
+
+#[test]
+fn importance_weights_reduce_variance_for_tail_probabilities() {
+    let target = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[2.0, 2.5]).unwrap();
+    let proposal = PiecewiseExponential::new(&[1.0], &[0.4]).unwrap();
+    let threshold = 3.0;
+    let truth = target.survival(threshold);
+    let n = 20_000;
+
+    let variance = |values: &[f64]| {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+        (mean, var)
+    };
+
+    let mut rng = StdRng::seed_from_u64(404);
+    let weighted: Vec<f64> = proposal
+        .sample_n(n, &mut rng)
+        .into_iter()
+        .map(|t| {
+            if t > threshold {
+                proposal.importance_weight(&target, t)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let direct: Vec<f64> = target
+        .sample_n(n, &mut rng)
+        .into_iter()
+        .map(|t| if t > threshold { 1.0 } else { 0.0 })
+        .collect();
+
+    let (is_mean, is_var) = variance(&weighted);
+    let (_, direct_var) = variance(&direct);
+
+    assert!((is_mean - truth).abs() < 0.1 * truth);
+    assert!(is_var < direct_var);
+}
+
+#[test]
+fn density_is_hazard_times_survival() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+
+    assert_eq!(dist.hazard_at(-0.1), 0.0);
+    assert_eq!(dist.hazard_at(0.0), 0.5);
+    assert_eq!(dist.hazard_at(1.0), 2.0);
+    assert_close_slice(
+        &[dist.density(0.5), dist.density(1.5)],
+        &[0.5 * (-0.25_f64).exp(), 2.0 * (-1.5_f64).exp()],
+    );
+}
+
+#[test]
+fn rmst_integrates_survival_across_intervals() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+
+    let first = (1.0 - (-0.5_f64).exp()) / 0.5;
+    let second = (-0.5_f64).exp() * (1.0 - (-2.0_f64).exp()) / 2.0;
+    assert_close_slice(&[dist.rmst(2.0)], &[first + second]);
+    assert_close_slice(
+        &[dist.rmst(f64::INFINITY)],
+        &[first + (-0.5_f64).exp() / 2.0],
+    );
+    assert_eq!(dist.rmst(0.0), 0.0);
+}
+
+#[test]
+fn exponential_for_rmst_reproduces_target() {
+    for (target, tau) in [(0.5, 24.0), (8.0, 12.0), (11.99, 12.0)] {
+        let dist = PiecewiseExponential::exponential_for_rmst(target, tau).unwrap();
+        assert!((dist.rmst(tau) - target).abs() < 1e-9 * tau);
+    }
+
+    for (target, tau) in [
+        (0.0, 12.0),
+        (12.0, 12.0),
+        (-1.0, 12.0),
+        (1.0, f64::INFINITY),
+    ] {
+        assert_eq!(
+            PiecewiseExponential::exponential_for_rmst(target, tau).unwrap_err(),
+            PiecewiseExponentialError::InvalidRmstTarget
+        );
+    }
+}
+
+#[test]
+fn quantile_fn_matches_inverse_cdf_and_clamps() {
+    let dist = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+    let quantile = dist.quantile_fn();
+
+    let uniforms: Vec<f64> = (1..=50).map(|i| i as f64 / 50.0).collect();
+    let via_closure: Vec<f64> = uniforms.iter().map(|&u| quantile(u)).collect();
+    let via_inverse: Vec<f64> = uniforms
+        .iter()
+        .map(|&u| dist.inverse_cdf(u).unwrap())
+        .collect();
+    assert_close_slice(&via_closure, &via_inverse);
+
+    assert_eq!(quantile(1.0), 0.0);
+    assert_eq!(quantile(2.0), 0.0);
+    let lowest = dist.inverse_cdf(f64::MIN_POSITIVE).unwrap();
+    assert_eq!(quantile(0.0), lowest);
+    assert_eq!(quantile(-3.0), lowest);
+    assert!(lowest.is_finite());
+    assert!(quantile(f64::NAN).is_nan());
+}
+
+#[test]
+fn moments_match_closed_forms() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    let mut rng = StdRng::seed_from_u64(8080);
+    let draws = dist.sample_n(200_000, &mut rng);
+
+    let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+    let variance = draws.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / draws.len() as f64;
+
+    assert!((dist.mean() - mean).abs() < 0.01);
+    assert!((dist.variance() - variance).abs() < 0.02);
+}
+
+#[test]
+fn coefficient_of_variation_is_one_for_exponential() {
+    for rate in [0.25, 0.5, 2.0, 8.0] {
+        let dist = PiecewiseExponential::new(&[1.0], &[rate]).unwrap();
+        assert_eq!(dist.coefficient_of_variation(), Some(1.0));
+    }
+    let dist = PiecewiseExponential::new(&[1.0], &[0.3]).unwrap();
+    assert!((dist.coefficient_of_variation().unwrap() - 1.0).abs() < 1e-12);
+
+    // An increasing hazard is less dispersed than an exponential.
+    let increasing = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.1, 3.0]).unwrap();
+    assert!(increasing.coefficient_of_variation().unwrap() < 1.0);
+}
+
+#[test]
+fn intervals_report_boundaries_and_rates() {
+    let dist = PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[1.0, 3.0, 0.5]).unwrap();
+    let intervals: Vec<Interval> = dist.intervals().collect();
+
+    assert_eq!(
+        intervals,
+        [
+            Interval {
+                index: 0,
+                start: 0.0,
+                end: 0.5,
+                rate: 1.0
+            },
+            Interval {
+                index: 1,
+                start: 0.5,
+                end: 2.0,
+                rate: 3.0
+            },
+            Interval {
+                index: 2,
+                start: 2.0,
+                end: f64::INFINITY,
+                rate: 0.5
+            },
+        ]
+    );
+
+    let finite = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+    let last = finite.intervals().last().unwrap();
+    assert_eq!((last.start, last.end), (1.0, 2.0));
+}
+
+#[test]
+fn truncate_renormalizes_mass_onto_bounded_support() {
+    let dist = PiecewiseExponential::new(&[1.0, 1.0, f64::INFINITY], &[0.5, 2.0, 1.0]).unwrap();
+    let t_max = 1.5;
+    let truncated = dist.truncate(t_max).unwrap();
+
+    let intervals: Vec<(f64, f64, f64)> = truncated
+        .intervals()
+        .map(|interval| (interval.start, interval.end, interval.rate))
+        .collect();
+    assert_eq!(intervals, [(0.0, 1.0, 0.5), (1.0, 1.5, 2.0)]);
+
+    let floor = dist.survival(t_max);
+    let times = [0.0, 0.25, 1.0, 1.2, 1.49];
+    let expected: Vec<f64> = times
+        .iter()
+        .map(|&t| (dist.survival(t) - floor) / (1.0 - floor))
+        .collect();
+    let actual: Vec<f64> = times.iter().map(|&t| truncated.survival(t)).collect();
+    assert_close_slice(&actual, &expected);
+    assert_eq!(truncated.survival(t_max), 0.0);
+    assert_eq!(truncated.cdf(2.0), 1.0);
+    assert_eq!(truncated.density(1.6), 0.0);
+
+    let uniforms: Vec<f64> = (1..=40).map(|i| i as f64 / 40.0).collect();
+    for &u in &uniforms {
+        let t = truncated.inverse_cdf(u).unwrap();
+        assert!(t >= 0.0 && t <= t_max);
+        assert!((truncated.survival(t) - u).abs() < 1e-12);
+    }
+
+    let mut rng = StdRng::seed_from_u64(1717);
+    let draws = truncated.sample_n(200_000, &mut rng);
+    assert!(draws.iter().all(|&t| t > 0.0 && t <= t_max));
+    let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+    let variance = draws.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / draws.len() as f64;
+    assert!((truncated.mean() - mean).abs() < 0.005);
+    assert!((truncated.variance() - variance).abs() < 0.005);
+    let below_one = draws.iter().filter(|&&t| t <= 1.0).count() as f64 / draws.len() as f64;
+    assert!((truncated.cdf(1.0) - below_one).abs() < 0.005);
+
+    let narrower = truncated.truncate(5.0).unwrap();
+    assert_eq!(narrower.survival(t_max), 0.0);
+
+    for t_max in [0.0, -1.0, f64::INFINITY, f64::NAN] {
+        assert_eq!(
+            dist.truncate(t_max).unwrap_err(),
+            PiecewiseExponentialError::InvalidTruncation
+        );
+    }
+}
+
+#[test]
+fn sample_total_matches_sum_of_draws_and_scaled_mean() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+
+    let mut rng_total = StdRng::seed_from_u64(1919);
+    let mut rng_draws = StdRng::seed_from_u64(1919);
+    let total = dist.sample_total(25, &mut rng_total);
+    let summed: f64 = dist.sample_n(25, &mut rng_draws).iter().sum();
+    assert_close_slice(&[total], &[summed]);
+
+    let m = 40;
+    let replicates = 20_000;
+    let average = (0..replicates)
+        .map(|_| dist.sample_total(m, &mut rng_total))
+        .sum::<f64>()
+        / replicates as f64;
+    let expected = m as f64 * dist.mean();
+    assert!((average - expected).abs() < 0.01 * expected);
+
+    assert_eq!(dist.sample_total(0, &mut rng_total), 0.0);
+}
+
+#[test]
+fn predicted_events_by_interval_match_observed_counts() {
+    let dist = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.1, 0.3, 0.05]).unwrap();
+    let mut rng = StdRng::seed_from_u64(2020);
+    let times = dist.sample_n(20_000, &mut rng);
+
+    let predicted = dist.predicted_events_by_interval(&times);
+    let mut observed = [0.0; 3];
+    for &time in &times {
+        let idx = if time < 2.0 {
+            0
+        } else if time < 6.0 {
+            1
+        } else {
+            2
+        };
+        observed[idx] += 1.0;
+    }
+
+    let total_hazard: f64 = times.iter().map(|&t| dist.cumulative_hazard_at(t)).sum();
+    assert_close_slice(&[predicted.iter().sum::<f64>()], &[total_hazard]);
+    for (&expected, &count) in predicted.iter().zip(&observed) {
+        assert!((count - expected).abs() < 4.0 * expected.sqrt());
+    }
+
+    assert_eq!(dist.predicted_events_by_interval(&[]), [0.0; 3]);
+    assert_eq!(dist.predicted_events_by_interval(&[0.0]), [0.0; 3]);
+
+    // The truncated hazard is integrated rather than taken as a constant rate.
+    let truncated = dist.truncate(5.0).unwrap();
+    let times = truncated.sample_n(2_000, &mut rng);
+    let predicted = truncated.predicted_events_by_interval(&times);
+    let total_hazard: f64 = times
+        .iter()
+        .map(|&t| truncated.cumulative_hazard_at(t))
+        .sum();
+    assert_close_slice(&[predicted.iter().sum::<f64>()], &[total_hazard]);
+}
+
+#[test]
+fn cox_snell_residuals_are_unit_exponential_under_the_model() {
+    let dist = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.1, 0.3, 0.05]).unwrap();
+    let mut rng = StdRng::seed_from_u64(2121);
+    let times = dist.sample_n(50_000, &mut rng);
+    let events = vec![true; times.len()];
+
+    let residuals: Vec<f64> = dist
+        .cox_snell_residuals(&times, &events)
+        .unwrap()
+        .into_iter()
+        .map(|(residual, _)| residual)
+        .collect();
+    let n = residuals.len() as f64;
+    let mean = residuals.iter().sum::<f64>() / n;
+    let variance = residuals.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+    assert!((mean - 1.0).abs() < 0.02);
+    assert!((variance - 1.0).abs() < 0.05);
+
+    let beyond_one = residuals.iter().filter(|&&r| r > 1.0).count() as f64 / n;
+    assert!((beyond_one - (-1.0_f64).exp()).abs() < 0.01);
+}
+
+#[test]
+fn cox_snell_residuals_carry_censoring_into_a_unit_exponential_fit() {
+    let dist = PiecewiseExponential::new(&[2.0, 4.0, f64::INFINITY], &[0.1, 0.3, 0.05]).unwrap();
+    let censoring = PiecewiseExponential::new(&[1.0], &[0.08]).unwrap();
+    let mut rng = StdRng::seed_from_u64(2122);
+    let failures = dist.sample_n(50_000, &mut rng);
+    let censored = censoring.sample_n(50_000, &mut rng);
+    let times: Vec<f64> = failures
+        .iter()
+        .zip(&censored)
+        .map(|(&failure, &censor)| failure.min(censor))
+        .collect();
+    let events: Vec<bool> = failures
+        .iter()
+        .zip(&censored)
+        .map(|(&failure, &censor)| failure <= censor)
+        .collect();
+
+    let mut residuals = dist.cox_snell_residuals(&times, &events).unwrap();
+    assert!(
+        residuals
+            .iter()
+            .zip(&events)
+            .all(|(pair, &event)| pair.1 == event)
+    );
+
+    // The Nelson-Aalen estimate of the residuals' cumulative hazard is the identity.
+    residuals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut at_risk = residuals.len() as f64;
+    let mut cumulative = 0.0;
+    for &(residual, event) in &residuals {
+        if residual > 1.0 {
+            break;
+        }
+        if event {
+            cumulative += 1.0 / at_risk;
+        }
+        at_risk -= 1.0;
+    }
+    assert!((cumulative - 1.0).abs() < 0.03);
+}
+
+#[test]
+fn cox_snell_residuals_reject_mismatched_lengths() {
+    let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    assert_eq!(
+        dist.cox_snell_residuals(&[1.0, 2.0], &[true]).unwrap_err(),
+        PiecewiseExponentialError::ObservationLengthMismatch {
+            times: 2,
+            events: 1
+        }
+    );
+}
+
+#[test]
+fn rescale_time_converts_years_to_months() {
+    let years = PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[0.8, 0.4, 0.2]).unwrap();
+    let months = years.rescale_time(12.0).unwrap();
+
+    let median_years = years.inverse_cdf(0.5).unwrap();
+    let median_months = months.inverse_cdf(0.5).unwrap();
+    assert_close_slice(&[median_months], &[12.0 * median_years]);
+
+    let ends: Vec<f64> = months.intervals().map(|interval| interval.end).collect();
+    assert_eq!(ends, [6.0, 24.0, f64::INFINITY]);
+    assert_close_slice(&[months.mean()], &[12.0 * years.mean()]);
+    for t in [0.25, 1.0, 3.0] {
+        assert_close_slice(&[months.survival(12.0 * t)], &[years.survival(t)]);
+    }
+
+    let truncated = years.truncate(2.0).unwrap().rescale_time(12.0).unwrap();
+    assert_eq!(truncated.survival(24.0), 0.0);
+
+    for factor in [0.0, -12.0, f64::INFINITY, f64::NAN] {
+        assert_eq!(
+            years.rescale_time(factor).unwrap_err(),
+            PiecewiseExponentialError::InvalidScaleFactor
+        );
+    }
+}
+
+#[test]
+fn survival_milestones_are_reproduced_exactly() {
+    let milestones = [(6.0, 0.7), (12.0, 0.4), (24.0, 0.2), (36.0, 0.15)];
+    let dist = PiecewiseExponential::from_survival_milestones(&milestones).unwrap();
+
+    let actual: Vec<f64> = milestones.iter().map(|&(t, _)| dist.survival(t)).collect();
+    let expected: Vec<f64> = milestones.iter().map(|&(_, s)| s).collect();
+    assert_close_slice(&actual, &expected);
+
+    let last = dist.intervals().last().unwrap();
+    assert_eq!((last.start, last.end), (24.0, f64::INFINITY));
+    assert_close_slice(&[last.rate], &[(0.2_f64 / 0.15).ln() / 12.0]);
+
+    assert_eq!(
+        PiecewiseExponential::from_survival_milestones(&[]).unwrap_err(),
+        PiecewiseExponentialError::EmptyIntervals
+    );
+    for (invalid, index) in [
+        (vec![(0.0, 0.5)], 0),
+        (vec![(12.0, 1.0)], 0),
+        (vec![(12.0, 0.0)], 0),
+        (vec![(12.0, 0.4), (12.0, 0.3)], 1),
+        (vec![(12.0, 0.4), (24.0, 0.4)], 1),
+        (vec![(12.0, 0.4), (f64::INFINITY, 0.2)], 1),
+        (vec![(12.0, 0.4), (6.0, 0.2)], 1),
+    ] {
+        assert_eq!(
+            PiecewiseExponential::from_survival_milestones(&invalid).unwrap_err(),
+            PiecewiseExponentialError::InvalidMilestone { index }
+        );
+    }
+}
+
+#[test]
+fn recorded_uniforms_replay_through_inverse_cdf() {
+    let dist = PiecewiseExponential::new(&[0.5, 0.5, 1.0], &[1.0, 3.0, 10.0]).unwrap();
+
+    let mut rng_pairs = StdRng::seed_from_u64(2525);
+    let mut rng_samples = StdRng::seed_from_u64(2525);
+    let pairs = dist.sample_n_with_uniforms(50, &mut rng_pairs);
+    let samples = dist.sample_n(50, &mut rng_samples);
+
+    let (uniforms, times): (Vec<f64>, Vec<f64>) = pairs.into_iter().unzip();
+    let replayed: Vec<f64> = uniforms
+        .iter()
+        .map(|&u| dist.inverse_cdf(u).unwrap())
+        .collect();
+    assert_close_slice(&replayed, &times);
+    assert_close_slice(&times, &samples);
+}
+
+#[test]
+fn hazard_shape_classifies_rate_sequences() {
+    let cases: [(&[f64], HazardShape); 8] = [
+        (&[0.3], HazardShape::Constant),
+        (&[0.3, 0.3, 0.3], HazardShape::Constant),
+        (&[0.1, 0.2, 0.2, 0.5], HazardShape::Increasing),
+        (&[0.5, 0.5, 0.2, 0.1], HazardShape::Decreasing),
+        (&[0.5, 0.1, 0.1, 0.4], HazardShape::Bathtub),
+        (&[0.5, 0.2, 0.1, 0.3, 0.6], HazardShape::Bathtub),
+        (&[0.1, 0.5, 0.2], HazardShape::Mixed),
+        (&[0.5, 0.1, 0.4, 0.2], HazardShape::Mixed),
+    ];
+    for (rates, shape) in cases {
+        let mut durations = vec![1.0; rates.len()];
+        *durations.last_mut().unwrap() = f64::INFINITY;
+        let dist = PiecewiseExponential::new(&durations, rates).unwrap();
+        assert_eq!(dist.hazard_shape(), shape, "rates {rates:?}");
+    }
+
+    // Truncation makes the hazard rise within every interval.
+    let constant = PiecewiseExponential::new(&[1.0], &[0.3]).unwrap();
+    assert_eq!(
+        constant.truncate(4.0).unwrap().hazard_shape(),
+        HazardShape::Increasing
+    );
+    let falling = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 0.1]).unwrap();
+    assert_eq!(
+        falling.truncate(4.0).unwrap().hazard_shape(),
+        HazardShape::Mixed
+    );
+}
+
+#[test]
+fn semicompeting_draws_are_ordered_with_expected_margins() {
+    let progression = PiecewiseExponential::new(&[6.0, f64::INFINITY], &[0.1, 0.2]).unwrap();
+    let death = PiecewiseExponential::new(&[1.0], &[0.04]).unwrap();
+
+    let mut rng_a = StdRng::seed_from_u64(3636);
+    let mut rng_b = StdRng::seed_from_u64(3636);
+    let n = 100_000;
+    let pairs: Vec<(f64, f64)> = (0..n)
+        .map(|_| progression.sample_semicompeting(&death, &mut rng_a))
+        .collect();
+    let replay: Vec<(f64, f64)> = (0..n)
+        .map(|_| progression.sample_semicompeting(&death, &mut rng_b))
+        .collect();
+    assert_eq!(pairs, replay);
+    assert!(pairs.iter().all(|&(pfs, os)| pfs <= os));
+
+    let os_mean = pairs.iter().map(|&(_, os)| os).sum::<f64>() / n as f64;
+    assert!((os_mean - death.mean()).abs() < 0.01 * death.mean());
+
+    let t = 9.0;
+    let pfs_survival = pairs.iter().filter(|&&(pfs, _)| pfs > t).count() as f64 / n as f64;
+    let expected = progression.survival(t) * death.survival(t);
+    assert!((pfs_survival - expected).abs() < 0.005);
+}
+
+#[test]
+fn cumulative_hazard_breakpoints_trace_the_integrated_hazard() {
+    let dist =
+        PiecewiseExponential::new(&[0.5, 1.5, 2.0, f64::INFINITY], &[1.0, 3.0, 0.5, 0.2]).unwrap();
+    let breakpoints = dist.cumulative_hazard_breakpoints();
+
+    assert_eq!(breakpoints.len(), dist.intervals().count());
+    assert_eq!(breakpoints[0], 0.0);
+    assert!(breakpoints.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    let at_starts: Vec<f64> = dist
+        .intervals()
+        .map(|interval| dist.cumulative_hazard_at(interval.start))
+        .collect();
+    assert_close_slice(breakpoints, &at_starts);
+
+    // Truncation keeps the breakpoints of the hazard before truncation, which the interval
+    // rates integrate to.
+    let truncated = dist.truncate(3.0).unwrap();
+    assert_eq!(truncated.cumulative_hazard_breakpoints(), &breakpoints[..3]);
+    let integrated: Vec<f64> = truncated
+        .intervals()
+        .scan(0.0, |total, interval| {
+            let start = *total;
+            *total += interval.rate * (interval.end - interval.start);
+            Some(start)
+        })
+        .collect();
+    assert_close_slice(truncated.cumulative_hazard_breakpoints(), &integrated);
+}
+
+#[test]
+fn gamma_frailty_thickens_the_tail() {
+    let dist = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.5, 1.0]).unwrap();
+
+    let mut rng_plain = StdRng::seed_from_u64(3939);
+    let mut rng_frailty = StdRng::seed_from_u64(3939);
+    let plain = dist.sample_n(10, &mut rng_plain);
+    let no_frailty: Vec<f64> = (0..10)
+        .map(|_| dist.sample_with_frailty(0.0, &mut rng_frailty).unwrap())
+        .collect();
+    assert_eq!(plain, no_frailty);
+
+    let n = 100_000;
+    let theta = 1.0;
+    let t = 4.0;
+    let frail: Vec<f64> = (0..n)
+        .map(|_| dist.sample_with_frailty(theta, &mut rng_frailty).unwrap())
+        .collect();
+    let beyond = frail.iter().filter(|&&draw| draw > t).count() as f64 / n as f64;
+
+    let marginal = (1.0 + theta * dist.cumulative_hazard_at(t)).powf(-1.0 / theta);
+    assert!((beyond - marginal).abs() < 0.005);
+    assert!(beyond > 5.0 * dist.survival(t));
+}
+
+#[test]
+fn gamma_frailty_stays_within_truncated_support() {
+    let t_max = 1.5;
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 1.0])
+        .unwrap()
+        .truncate(t_max)
+        .unwrap();
+    let mut rng = StdRng::seed_from_u64(4390);
+    for theta in [0.25, 1.0, 20.0] {
+        for _ in 0..20_000 {
+            let draw = dist.sample_with_frailty(theta, &mut rng).unwrap();
+            assert!((0.0..=t_max).contains(&draw), "draw {draw} outside support");
+        }
+    }
+
+    // Without frailty heterogeneity the draws follow the truncated law itself.
+    let n = 100_000;
+    let below = (0..n)
+        .filter(|_| dist.sample_with_frailty(1e-9, &mut rng).unwrap() <= 1.0)
+        .count() as f64
+        / n as f64;
+    assert!((below - dist.cdf(1.0)).abs() < 0.005);
+}
+
+#[test]
+fn gamma_frailty_rejects_invalid_variance() {
+    let dist = PiecewiseExponential::new(&[1.0], &[0.5]).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    for variance in [-1.0, f64::INFINITY, f64::NAN] {
+        assert!(matches!(
+            dist.sample_with_frailty(variance, &mut rng),
+            Err(PiecewiseExponentialSampleError::InvalidFrailtyVariance { .. })
+        ));
+    }
+}
+
+#[test]
+fn has_open_tail_reflects_final_duration() {
+    let open = PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[1.0, 3.0, 0.5]).unwrap();
+    assert!(open.has_open_tail());
+    assert!(
+        PiecewiseExponential::new(&[f64::INFINITY], &[1.0])
+            .unwrap()
+            .has_open_tail()
+    );
+
+    let finite = PiecewiseExponential::new(&[0.5, 1.5, 2.0], &[1.0, 3.0, 0.5]).unwrap();
+    assert!(!finite.has_open_tail());
+    assert!(
+        !PiecewiseExponential::new(&[1.0], &[1.0])
+            .unwrap()
+            .has_open_tail()
+    );
+    assert!(!open.truncate(3.0).unwrap().has_open_tail());
+    assert!(open.rescale_time(12.0).unwrap().has_open_tail());
+}
+
+#[test]
+fn halton_sample_estimates_the_mean_more_accurately_than_pseudo_random() {
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    let n = 4096;
+    let truth = dist.mean();
+    let mean_error = |draws: &[f64]| (draws.iter().sum::<f64>() / draws.len() as f64 - truth).abs();
+
+    let halton = dist.sample_halton(n, 2).unwrap();
+    assert_eq!(halton, dist.sample_halton(n, 2).unwrap());
+    let halton_error = mean_error(&halton);
+
+    let seeds = 20;
+    let random_error = (0..seeds)
+        .map(|seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            mean_error(&dist.sample_n(n, &mut rng))
+        })
+        .sum::<f64>()
+        / seeds as f64;
+    assert!(halton_error < random_error);
+    assert!(dist.sample_halton(n, 3).unwrap().iter().all(|&t| t > 0.0));
+
+    for base in [0, 1] {
+        assert_eq!(
+            dist.sample_halton(n, base).unwrap_err(),
+            PiecewiseExponentialSampleError::InvalidHaltonBase { base }
+        );
+    }
+}
+
+#[test]
+fn statistically_eq_ignores_split_intervals() {
+    let coarse = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.3, 0.1]).unwrap();
+    let split =
+        PiecewiseExponential::new(&[0.5, 1.5, 3.0, f64::INFINITY], &[0.3, 0.3, 0.1, 0.1]).unwrap();
+
+    assert_ne!(coarse.intervals().count(), split.intervals().count());
+    assert!(coarse.statistically_eq(&split, 1e-12));
+    assert!(split.statistically_eq(&coarse, 1e-12));
+
+    let nudged =
+        PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[0.3, 0.3 + 1e-6, 0.1]).unwrap();
+    assert!(!coarse.statistically_eq(&nudged, 1e-9));
+    assert!(coarse.statistically_eq(&nudged, 1e-5));
+
+    let shifted = PiecewiseExponential::new(&[2.5, f64::INFINITY], &[0.3, 0.1]).unwrap();
+    assert!(!coarse.statistically_eq(&shifted, 1e-12));
+    assert!(!coarse.statistically_eq(&coarse.truncate(5.0).unwrap(), 1e-12));
+    assert!(
+        coarse
+            .truncate(5.0)
+            .unwrap()
+            .statistically_eq(&split.truncate(5.0).unwrap(), 1e-12)
+    );
+}
+
+#[test]
+fn conditional_survival_is_memoryless_for_exponential() {
+    let exponential = PiecewiseExponential::new(&[1.0], &[0.3]).unwrap();
+    for s in [0.0, 1.0, 5.0, 40.0] {
+        for gap in [0.5, 2.0, 10.0] {
+            assert_close_slice(
+                &[exponential.conditional_survival(s + gap, s)],
+                &[exponential.survival(gap)],
+            );
+        }
+    }
+
+    let dist = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 2.0]).unwrap();
+    assert_close_slice(
+        &[dist.conditional_survival(3.0, 0.5)],
+        &[dist.survival(3.0) / dist.survival(0.5)],
+    );
+    assert!(dist.conditional_survival(401.0, 400.0) > 0.0);
+    assert_eq!(dist.conditional_survival(2.0, 2.0), 1.0);
+    assert_eq!(dist.conditional_survival(1.0, 2.0), 1.0);
+}
+
+#[test]
+fn hazard_steps_reproduce_rates_and_boundaries() {
+    let dist = PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[1.0, 3.0, 0.5]).unwrap();
+
+    assert_eq!(
+        dist.hazard_steps_to(24.0).unwrap(),
+        [(0.0, 0.5, 1.0), (0.5, 2.0, 3.0), (2.0, 24.0, 0.5)]
+    );
+
+    let steps = dist.hazard_steps().unwrap();
+    let tail_end = steps[2].1;
+    assert!(tail_end >= 2.0 + 1.0 / 0.5);
+    assert!(dist.survival(tail_end) <= 0.001 + 1e-12);
+    for &(start, end, rate) in &steps {
+        assert!(start < end);
+        assert_eq!(dist.hazard_at(0.5 * (start + end)), rate);
+    }
+
+    // A tail that starts after nearly all the mass still gets a visible segment.
+    let late = PiecewiseExponential::new(&[20.0, f64::INFINITY], &[1.0, 0.1]).unwrap();
+    assert_eq!(late.hazard_steps().unwrap()[1], (20.0, 30.0, 0.1));
+
+    let finite = PiecewiseExponential::new(&[0.5, 1.5], &[1.0, 3.0]).unwrap();
+    assert_eq!(
+        finite.hazard_steps().unwrap(),
+        [(0.0, 0.5, 1.0), (0.5, 2.0, 3.0)]
+    );
+}
+
+#[test]
+fn hazard_steps_reject_truncated_distributions() {
+    let dist = PiecewiseExponential::new(&[0.5, 1.5, f64::INFINITY], &[1.0, 3.0, 0.5]).unwrap();
+    let truncated = dist.truncate(1.0).unwrap();
+    assert!(truncated.hazard_steps().is_err());
+    assert_eq!(
+        truncated.hazard_steps_to(24.0).unwrap_err(),
+        PiecewiseExponentialError::TruncatedHazard
+    );
+}
+
+#[test]
+fn quantile_modes_control_extrapolation_past_finite_boundary() {
+    let finite = PiecewiseExponential::new(&[1.0, 1.0], &[0.5, 1.0]).unwrap();
+    // F(2) = 1 - exp(-1.5) is about 0.777, so p = 0.95 lies past the final boundary.
+    let p = 0.95;
+    let extrapolated = 2.0 + (-(1.0_f64 - p).ln() - 1.5) / 1.0;
+
+    assert_eq!(finite.quantile_with(p, QuantileMode::Clamp).unwrap(), 2.0);
+    assert_close_slice(
+        &[finite.quantile_with(p, QuantileMode::Extrapolate).unwrap()],
+        &[extrapolated],
+    );
+    assert_eq!(
+        finite.quantile_with(p, QuantileMode::Error).unwrap_err(),
+        PiecewiseExponentialSampleError::BeyondFinalBoundary {
+            probability: p,
+            boundary: 2.0
+        }
+    );
+
+    // Inside the support every mode agrees with the inverse CDF.
+    for mode in [
+        QuantileMode::Clamp,
+        QuantileMode::Extrapolate,
+        QuantileMode::Error,
+    ] {
+        assert_close_slice(
+            &[finite.quantile_with(0.5, mode).unwrap()],
+            &[finite.inverse_cdf(0.5).unwrap()],
+        );
+        assert_eq!(finite.quantile_with(0.0, mode).unwrap(), 0.0);
+    }
+
+    let open = PiecewiseExponential::new(&[1.0, f64::INFINITY], &[0.5, 1.0]).unwrap();
+    assert!(open.quantile_with(p, QuantileMode::Error).is_ok());
+    let truncated = open.truncate(2.0).unwrap();
+    assert!(truncated.quantile_with(0.999, QuantileMode::Error).unwrap() <= 2.0);
+
+    for value in [1.0, -0.1, f64::NAN] {
+        assert!(matches!(
+            finite.quantile_with(value, QuantileMode::Clamp),
+            Err(PiecewiseExponentialSampleError::ProbabilityOutOfRange { .. })
+        ));
+    }
+}
+
+#[test]
+fn prob_no_events_matches_naive_power_and_handles_large_cohorts() {
+    let dist = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.01, 0.03]).unwrap();
+
+    for (n, t) in [(1, 0.5), (10, 1.0), (250, 3.0), (1000, 10.0)] {
+        let naive = dist.survival(t).powi(n as i32);
+        assert_close_slice(&[dist.prob_no_events(n, t)], &[naive]);
+    }
+
+    // Beyond `i32::MAX` patients the naive exponent would overflow.
+    let huge = 1_usize << 40;
+    let expected = (-(huge as f64) * 0.01 * 1e-12).exp();
+    assert_close_slice(&[dist.prob_no_events(huge, 1e-12)], &[expected]);
+
+    assert_eq!(dist.prob_no_events(0, 5.0), 1.0);
+    assert_eq!(dist.prob_no_events(50, 0.0), 1.0);
+    let truncated = dist.truncate(4.0).unwrap();
+    assert_eq!(truncated.prob_no_events(0, 4.0), 1.0);
+    assert_eq!(truncated.prob_no_events(1, 4.0), 0.0);
+}
+
+#[test]
+fn blend_with_constant_weights_recovers_each_input() {
+    let a = PiecewiseExponential::new(&[2.0, 3.0, f64::INFINITY], &[0.3, 0.1, 0.2]).unwrap();
+    let b = PiecewiseExponential::new(&[1.0, 5.0], &[0.05, 0.4]).unwrap();
+
+    let all_a = PiecewiseExponential::blend(&a, &b, |_| 0.0).unwrap();
+    let all_b = PiecewiseExponential::blend(&a, &b, |_| 1.0).unwrap();
+    assert!(all_a.statistically_eq(&a, 1e-15));
+    assert!(all_b.statistically_eq(&b, 1e-15));
+
+    // Weights outside [0, 1] are clamped.
+    let clamped =
+        PiecewiseExponential::blend(&a, &b, |t| if t < 3.0 { -2.0 } else { 7.0 }).unwrap();
+    assert_eq!(clamped.hazard_at(0.5), a.hazard_at(0.5));
+    assert_eq!(clamped.hazard_at(4.0), b.hazard_at(4.0));
+
+    // Weights come from the midpoints of [0, 1), [1, 2), and [2, 5), and the start of the tail.
+    let ramp = PiecewiseExponential::blend(&a, &b, |t| t / 10.0).unwrap();
+    let expected: Vec<f64> = [
+        (0.5, 0.3, 0.05),
+        (1.5, 0.3, 0.4),
+        (3.5, 0.1, 0.4),
+        (5.0, 0.2, 0.4),
+    ]
+    .iter()
+    .map(|&(t, rate_a, rate_b)| (1.0 - t / 10.0) * rate_a + t / 10.0 * rate_b)
+    .collect();
+    let rates: Vec<f64> = ramp.intervals().map(|interval| interval.rate).collect();
+    assert_close_slice(&rates, &expected);
+    assert!(ramp.has_open_tail());
+
+    assert_eq!(
+        PiecewiseExponential::blend(&a, &b, |_| f64::NAN).unwrap_err(),
+        PiecewiseExponentialError::NonFiniteRate { index: 0 }
+    );
+}
+
+#[test]
+fn blend_keeps_a_truncated_input_only_at_full_weight() {
+    let truncated = PiecewiseExponential::new(&[2.0, f64::INFINITY], &[0.3, 0.1])
+        .unwrap()
+        .truncate(4.0)
+        .unwrap();
+    let other = PiecewiseExponential::new(&[1.0, 5.0], &[0.05, 0.4]).unwrap();
+
+    let all_first = PiecewiseExponential::blend(&truncated, &other, |_| 0.0).unwrap();
+    let all_second = PiecewiseExponential::blend(&other, &truncated, |_| 1.0).unwrap();
+    for blended in [&all_first, &all_second] {
+        assert!(blended.statistically_eq(&truncated, 0.0));
+        assert_eq!(blended.survival(5.0), 0.0);
+        assert_eq!(blended.survival(3.0), truncated.survival(3.0));
+    }
+    let untruncated = PiecewiseExponential::blend(&truncated, &other, |_| 1.0).unwrap();
+    assert!(untruncated.statistically_eq(&other, 0.0));
+
+    for weight in [0.5, 2.0 / 3.0] {
+        assert_eq!(
+            PiecewiseExponential::blend(&truncated, &other, |_| weight).unwrap_err(),
+            PiecewiseExponentialError::TruncatedHazard
+        );
+    }
+    assert_eq!(
+        PiecewiseExponential::blend(&truncated, &other, |t| t / 10.0).unwrap_err(),
+        PiecewiseExponentialError::TruncatedHazard
+    );
+}
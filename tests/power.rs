@@ -0,0 +1,55 @@
+use simtrial::{detectable_hr, events_for_power, power_confidence_interval};
+
+#[test]
+fn power_interval_covers_estimate_and_narrows_with_replicates() {
+    let rejections = |n: usize| -> Vec<bool> { (0..n).map(|i| i % 5 != 0).collect() };
+
+    let (small_lower, small_upper) = power_confidence_interval(&rejections(100), 0.95, 4000, 11);
+    let (large_lower, large_upper) = power_confidence_interval(&rejections(10_000), 0.95, 4000, 11);
+
+    assert!(small_lower < 0.8 && 0.8 < small_upper);
+    assert!(large_lower < 0.8 && 0.8 < large_upper);
+    assert!(large_upper - large_lower < 0.25 * (small_upper - small_lower));
+
+    // The binomial standard error puts the 95% interval near 0.8 +/- 1.96 * 0.04.
+    assert!((small_lower - 0.72).abs() < 0.02);
+    assert!((small_upper - 0.88).abs() < 0.02);
+
+    assert_eq!(
+        power_confidence_interval(&rejections(100), 0.9, 500, 3),
+        power_confidence_interval(&rejections(100), 0.9, 500, 3)
+    );
+    assert_eq!(
+        power_confidence_interval(&[true; 50], 0.95, 100, 1),
+        (1.0, 1.0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "confidence level must lie strictly between 0 and 1")]
+fn power_interval_rejects_invalid_level() {
+    power_confidence_interval(&[true, false], 1.0, 100, 1);
+}
+
+#[test]
+fn detectable_hr_round_trips_with_events_for_power() {
+    for (events, alpha, power, allocation) in [
+        (100.0, 0.025, 0.8, 0.5),
+        (330.0, 0.025, 0.9, 0.5),
+        (512.0, 0.005, 0.85, 2.0 / 3.0),
+    ] {
+        let hr = detectable_hr(events, alpha, power, allocation);
+        assert!(hr < 1.0);
+        let round_trip = events_for_power(hr, alpha, power, allocation);
+        assert!((round_trip - events).abs() < 1e-9 * events);
+    }
+
+    // More events detect smaller effects.
+    assert!(detectable_hr(400.0, 0.025, 0.9, 0.5) > detectable_hr(100.0, 0.025, 0.9, 0.5));
+}
+
+#[test]
+#[should_panic(expected = "events must be positive and finite")]
+fn detectable_hr_rejects_non_positive_events() {
+    detectable_hr(0.0, 0.025, 0.9, 0.5);
+}
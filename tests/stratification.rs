@@ -0,0 +1,91 @@
+use simtrial::{PiecewiseExponential, stratification_design_effect};
+
+mod common;
+
+use common::assert_close_slice;
+
+#[test]
+fn identical_strata_have_unit_design_effect() {
+    let control = PiecewiseExponential::new(&[6.0, f64::INFINITY], &[0.1, 0.08]).unwrap();
+    let experimental = PiecewiseExponential::new(&[3.0, f64::INFINITY], &[0.1, 0.05]).unwrap();
+
+    let single = stratification_design_effect(&[(1.0, &control, &experimental)], 36.0);
+    let identical = stratification_design_effect(
+        &[
+            (0.2, &control, &experimental),
+            (0.5, &control, &experimental),
+            (0.3, &control, &experimental),
+        ],
+        36.0,
+    );
+    assert_close_slice(&[single, identical], &[1.0, 1.0]);
+}
+
+#[test]
+fn prognostic_strata_change_the_log_rank_variance_under_an_effect() {
+    let low_ctrl = PiecewiseExponential::new(&[1.0], &[0.05]).unwrap();
+    let low_exp = PiecewiseExponential::new(&[1.0], &[0.025]).unwrap();
+    let high_ctrl = PiecewiseExponential::new(&[1.0], &[0.4]).unwrap();
+    let high_exp = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+
+    let effect = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_exp), (1.0, &high_ctrl, &high_exp)],
+        24.0,
+    );
+    assert!(effect.is_finite());
+    assert!((effect - 1.0).abs() > 1e-3);
+
+    // Without a treatment effect every risk set is balanced, stratified or not.
+    let null = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_ctrl), (3.0, &high_ctrl, &high_ctrl)],
+        24.0,
+    );
+    assert_close_slice(&[null], &[1.0]);
+}
+
+#[test]
+fn truncated_arms_are_integrated_through_their_truncation_points() {
+    let low_ctrl = PiecewiseExponential::new(&[1.0], &[0.05]).unwrap();
+    let low_exp = PiecewiseExponential::new(&[1.0], &[0.025]).unwrap();
+    let high_ctrl = PiecewiseExponential::new(&[1.0], &[0.4]).unwrap();
+    let high_exp = PiecewiseExponential::new(&[1.0], &[0.2]).unwrap();
+
+    // A truncation point far beyond the mass leaves the design effect unchanged.
+    let far_exp = high_exp.truncate(1_000.0).unwrap();
+    let plain = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_exp), (1.0, &high_ctrl, &high_exp)],
+        24.0,
+    );
+    let far = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_exp), (1.0, &high_ctrl, &far_exp)],
+        24.0,
+    );
+    assert_close_slice(&[far], &[plain]);
+
+    // Follow-up past the truncation point stays finite, and balanced strata stay balanced.
+    let truncated = high_ctrl.truncate(4.0).unwrap();
+    let effect = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_exp), (1.0, &truncated, &high_exp)],
+        10.0,
+    );
+    assert!(effect.is_finite() && effect > 0.0);
+    let null = stratification_design_effect(
+        &[(1.0, &low_ctrl, &low_ctrl), (1.0, &truncated, &truncated)],
+        10.0,
+    );
+    assert_close_slice(&[null], &[1.0]);
+}
+
+#[test]
+#[should_panic(expected = "tau must be positive and finite")]
+fn design_effect_rejects_an_infinite_horizon() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    stratification_design_effect(&[(1.0, &control, &control)], f64::INFINITY);
+}
+
+#[test]
+#[should_panic(expected = "tau must be positive and finite")]
+fn design_effect_rejects_a_non_positive_horizon() {
+    let control = PiecewiseExponential::new(&[1.0], &[0.1]).unwrap();
+    stratification_design_effect(&[(1.0, &control, &control)], 0.0);
+}
@@ -0,0 +1,22 @@
+use simtrial::fh_weights;
+
+mod common;
+
+use common::assert_close_slice;
+
+#[test]
+fn fh_weights_reduce_to_log_rank_and_follow_pooled_survival() {
+    let pooled = [1.0, 0.9, 0.6, 0.25, 0.0];
+
+    assert_eq!(fh_weights(&pooled, 0.0, 0.0), [1.0; 5]);
+    assert_close_slice(&fh_weights(&pooled, 1.0, 0.0), &pooled);
+    assert_close_slice(
+        &fh_weights(&pooled, 0.0, 1.0),
+        &[0.0, 0.09999999999999998, 0.4, 0.75, 1.0],
+    );
+    assert_close_slice(
+        &fh_weights(&pooled, 0.5, 0.5),
+        &[0.0, 0.3, 0.4898979485566356, 0.4330127018922193, 0.0],
+    );
+    assert!(fh_weights(&[], 1.0, 1.0).is_empty());
+}